@@ -0,0 +1,22 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use pbc4rust::prelude::*;
+
+/// a Type A curve with a group order of 160 bits and a base field of 512
+/// bits, like PBC's `a.param`
+fn pairing() -> Pairing {
+    Pairing::new(&TypeACurveGenerator::new(160, 512).generate())
+}
+
+fn pairing_preprocessed(c: &mut Criterion) {
+    let pairing = pairing();
+    let p = pairing.g1().random_element();
+    let q = pairing.g1().random_element();
+    let pp = PairingPreprocessed::new(&pairing, &p);
+
+    c.bench_function("pairing_pair_512", |bench| bench.iter(|| pairing.pair(black_box(&p), black_box(&q))));
+    c.bench_function("pairing_apply_512", |bench| bench.iter(|| pp.apply(black_box(&q))));
+    c.bench_function("pairing_preprocess_512", |bench| bench.iter(|| PairingPreprocessed::new(&pairing, black_box(&p))));
+}
+
+criterion_group!(benches, pairing_preprocessed);
+criterion_main!(benches);
@@ -0,0 +1,26 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use pbc4rust::prelude::*;
+use std::sync::Arc;
+
+/// the Mersenne prime 2^521 - 1
+fn field() -> Arc<ZrField> {
+    let mut order = Integer::zero();
+    order.setbit(521);
+    Arc::new(ZrField::new(order - Integer::from(1)))
+}
+
+fn zr(c: &mut Criterion) {
+    let field = field();
+    let a = Arc::clone(&field).random_element();
+    let b = Arc::clone(&field).random_element();
+    let exp = field.order() - Integer::from(3);
+
+    c.bench_function("zr_mul_521", |bench| bench.iter(|| black_box(&a) * black_box(&b)));
+    c.bench_function("zr_add_521", |bench| bench.iter(|| black_box(&a) + black_box(&b)));
+    c.bench_function("zr_pow_521", |bench| bench.iter(|| black_box(&a).pow(black_box(&exp))));
+    c.bench_function("zr_invert_521", |bench| bench.iter(|| black_box(&a).invert()));
+    c.bench_function("zr_new_521", |bench| bench.iter(|| Zr::new(black_box(exp.clone()), Arc::clone(&field))));
+}
+
+criterion_group!(benches, zr);
+criterion_main!(benches);
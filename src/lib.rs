@@ -1,6 +1,81 @@
+//! PBC (pairing based cryptography) implementation using Rust.
+//!
+//! The public surface of the crate is organized in the following modules:
+//!
+//!  - [`field`] contains the element types (`Z`, `Zr`, `Quadratic`), their
+//!    fields and the algebraic traits which connect them,
+//!  - [`curve`] contains the curve parameters, parameter generators and
+//!    the groups of curve points,
+//!  - [`pairing`] contains the bilinear maps between these groups,
+//!  - [`prelude`] reexports everything that is needed for everyday use.
+//!
+//! ```
+//! use pbc4rust::prelude::*;
+//! use std::sync::Arc;
+//!
+//! let field = Arc::new(ZrField::new(Integer::from(44497)));
+//! let a = Zr::new(Integer::from(3), Arc::clone(&field));
+//! let b = Zr::new(Integer::from(5), Arc::clone(&field));
+//! assert_eq!(a * b, Zr::new(Integer::from(15), field));
+//! ```
+
+#[cfg(not(any(feature = "backend-gmp", feature = "backend-num-bigint")))]
+compile_error!("one of the features \"backend-gmp\" and \"backend-num-bigint\" must be enabled");
+
+#[cfg(all(feature = "backend-gmp", feature = "backend-num-bigint"))]
+compile_error!("the features \"backend-gmp\" and \"backend-num-bigint\" are mutually exclusive, \
+    use `--no-default-features --features backend-num-bigint` for the latter");
+
+// everything depends on `Integer`, so without a backend only the error
+// above is reported
+#[cfg(any(feature = "backend-gmp", feature = "backend-num-bigint"))]
 #[macro_use]
 extern crate impl_ops;
 
+#[cfg(any(feature = "backend-gmp", feature = "backend-num-bigint"))]
 mod pbc;
 
-//pub use pbc;
+/// elements, fields and the traits implemented by them
+#[cfg(any(feature = "backend-gmp", feature = "backend-num-bigint"))]
+pub mod field {
+    pub use crate::pbc::{Z, ZField};
+    pub use crate::pbc::{Zr, ZrField};
+    pub use crate::pbc::{Quadratic, QuadraticField};
+    pub use crate::pbc::{Fp, FpField, FieldParams};
+    pub use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+    pub use crate::pbc::{BytesError, ByteLength, ToBytes, FromBytes};
+    pub use crate::pbc::PbcError;
+    pub use crate::pbc::SecretZr;
+    pub use crate::pbc::{Integer, ProbabPrimeResult};
+    pub use crate::pbc::{PowPreprocessed, multi_exp};
+    #[cfg(feature = "serde")]
+    pub use crate::pbc::ElementSeed;
+    pub use crate::pbc::traits::*;
+}
+
+/// curve parameters and their generators
+#[cfg(any(feature = "backend-gmp", feature = "backend-num-bigint"))]
+pub mod curve {
+    pub use crate::pbc::{CurveType, CurveOptions, ParamError};
+    pub use crate::pbc::{TypeACurveGenerator, TypeA1CurveGenerator};
+    pub use crate::pbc::{CurveField, CurvePoint};
+}
+
+/// bilinear maps
+#[cfg(any(feature = "backend-gmp", feature = "backend-num-bigint"))]
+pub mod pairing {
+    pub use crate::pbc::{Pairing, PairingPreprocessed};
+    pub use crate::pbc::{GT, GTField};
+}
+
+/// reexports all commonly used types and traits
+///
+/// ```
+/// use pbc4rust::prelude::*;
+/// ```
+#[cfg(any(feature = "backend-gmp", feature = "backend-num-bigint"))]
+pub mod prelude {
+    pub use crate::field::*;
+    pub use crate::curve::*;
+    pub use crate::pairing::*;
+}
@@ -0,0 +1,130 @@
+use crate::pbc::Integer;
+use super::{CurveOptions, CurvePoint};
+use crate::pbc::elements::traits::*;
+use crate::pbc::elements::{ByteLength, Zr, ZrField};
+use rand::{CryptoRng, RngCore};
+use std::sync::Arc;
+
+/// group of points on the curve y² = x³ + ax + b over some `ZrField`.
+///
+/// Only the subgroup of order `order` is used; `cofactor` maps arbitrary
+/// curve points into this subgroup.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurveField {
+    target_field: Arc<ZrField>,
+    a: Zr,
+    b: Zr,
+    order: Integer,
+    cofactor: Integer,
+}
+
+impl CurveField {
+    pub fn new(target_field: Arc<ZrField>, a: Zr, b: Zr, order: Integer, cofactor: Integer) -> CurveField {
+        assert_eq!(a.field(), target_field);
+        assert_eq!(b.field(), target_field);
+        CurveField {
+            target_field,
+            a,
+            b,
+            order,
+            cofactor,
+        }
+    }
+
+    /// creates the group G1 of a Type A or Type A1 curve, i.e. y² = x³ + x
+    /// over F_q.
+    ///
+    /// Because q ≡ 3 mod 4, -1 is used as quadratic nonresidue of F_q, so
+    /// that `Quadratic` elements over the coordinate field are F_q[i].
+    pub fn type_a(options: &CurveOptions) -> CurveField {
+        let target_field = Arc::new(ZrField::with_nqr(options.q.clone(), Integer::from(-1)));
+        let a = Arc::clone(&target_field).one_element();
+        let b = Arc::clone(&target_field).zero_element();
+        CurveField::new(target_field, a, b, options.r.clone(), options.h.clone())
+    }
+
+    pub fn a(&self) -> &Zr { &self.a }
+    pub fn b(&self) -> &Zr { &self.b }
+    pub fn cofactor(&self) -> &Integer { &self.cofactor }
+
+    /// calculates x³ + ax + b
+    pub fn rhs(&self, x: &Zr) -> Zr {
+        x.square() * x + &(self.a.clone() * x) + &self.b
+    }
+
+    pub fn contains(&self, x: &Zr, y: &Zr) -> bool {
+        y.square() == self.rhs(x)
+    }
+
+    /// maps arbitrary data to a point of the subgroup of order `order`, like
+    /// PBC's `element_from_hash`.
+    ///
+    /// Starting with x = `ZrField::from_hash(data)`, x is incremented until
+    /// x³ + ax + b is a square. Of both square roots, the even one is used
+    /// as y, before the point is multiplied by the cofactor.
+    pub fn from_hash(self: Arc<Self>, data: &[u8]) -> CurvePoint {
+        let one = Arc::clone(&self.target_field).one_element();
+        let mut x = Arc::clone(&self.target_field).from_hash(data);
+        loop {
+            if let Some((y, neg_y)) = self.rhs(&x).sqrt() {
+                let y = if y.value().tstbit(0) { neg_y } else { y };
+                let point = CurvePoint::new(x.clone(), y, Arc::clone(&self)).mul_mpz(&self.cofactor);
+                if ! point.is_infinity() {
+                    return point;
+                }
+            }
+            x = x + &one;
+        }
+    }
+}
+
+impl HasZero<CurvePoint> for CurveField {
+    fn zero_element(self: Arc<Self>) -> CurvePoint {
+        CurvePoint::infinity(self)
+    }
+}
+
+/// PBC uses multiplicative notation for all groups, so the neutral element
+/// of the group operation is also the point at infinity
+impl HasOne<CurvePoint> for CurveField {
+    fn one_element(self: Arc<Self>) -> CurvePoint {
+        CurvePoint::infinity(self)
+    }
+}
+
+impl Field<CurvePoint, ComplexElement> for CurveField {
+    fn random_element_with<R: RngCore + CryptoRng>(self: Arc<Self>, rng: &mut R) -> CurvePoint {
+        loop {
+            let x = Arc::clone(&self.target_field).random_element_with(rng);
+            if let Some((y, _)) = self.rhs(&x).sqrt() {
+                let point = CurvePoint::new(x, y, Arc::clone(&self)).mul_mpz(&self.cofactor);
+                if ! point.is_infinity() {
+                    return point;
+                }
+            }
+        }
+    }
+}
+
+impl ByteLength for CurveField {
+    fn length_in_bytes(&self) -> usize {
+        2 * self.target_field.length_in_bytes()
+    }
+}
+
+impl CurveField {
+    /// size of the x coordinate plus one byte for the sign of y
+    pub fn length_in_bytes_compressed(&self) -> usize {
+        self.target_field.length_in_bytes() + 1
+    }
+}
+
+impl FiniteField<CurvePoint, ComplexElement> for CurveField {
+    fn order(&self) -> &Integer { &self.order }
+}
+
+impl FieldOver<CurvePoint, CurveField, Zr, ZrField, AtomicElement> for CurveField {
+    fn target_field(&self) -> Arc<ZrField> {
+        Arc::clone(&self.target_field)
+    }
+}
@@ -0,0 +1,573 @@
+use crate::pbc::{BigInteger, Integer, PbcError};
+use super::CurveField;
+use crate::pbc::elements::traits::*;
+use crate::pbc::elements::{Zr, ZrField, Wiped, BytesError, ByteLength, ToBytes, FromBytes, check_length};
+use std::ops::{self, Neg};
+use std::sync::Arc;
+use subtle::Choice;
+
+/// point on an elliptic curve in affine coordinates.
+///
+/// As in PBC, the group operation is written multiplicatively as well as
+/// additively, i.e. `P * Q` and `P + Q` both denote point addition.
+#[derive(Debug, Clone)]
+pub struct CurvePoint {
+    x: Zr,
+    y: Zr,
+    infinity: bool,
+    field: Arc<CurveField>,
+}
+
+impl CurvePoint {
+    /// creates the point (x, y), which is not checked to be on the curve
+    pub fn new(x: Zr, y: Zr, field: Arc<CurveField>) -> CurvePoint {
+        CurvePoint {
+            x,
+            y,
+            infinity: false,
+            field,
+        }
+    }
+
+    pub fn infinity(field: Arc<CurveField>) -> CurvePoint {
+        let zero = field.target_field().zero_element();
+        CurvePoint {
+            x: zero.clone(),
+            y: zero,
+            infinity: true,
+            field,
+        }
+    }
+
+    pub fn x(&self) -> &Zr { &self.x }
+    pub fn y(&self) -> &Zr { &self.y }
+    pub fn is_infinity(&self) -> bool { self.infinity }
+
+    /// checks if the point lies on the curve
+    pub fn is_valid(&self) -> bool {
+        self.infinity || self.field.contains(&self.x, &self.y)
+    }
+
+    /// scalar multiplication, which is `pow` as well as `mul_zn` in PBC's
+    /// notation
+    pub fn mul_mpz(&self, n: &Integer) -> CurvePoint {
+        if n < &Integer::zero() {
+            return self.clone().neg().mul_mpz(&-n);
+        }
+
+        let mut result = CurvePoint::infinity(Arc::clone(&self.field));
+        for i in (0..n.bit_length()).rev() {
+            result = result.double();
+            if n.tstbit(i) {
+                result = &result + self;
+            }
+        }
+        result
+    }
+
+    /// scalar multiplication with a Montgomery ladder in projective
+    /// coordinates, which performs the same field operations for all
+    /// scalars of the field of `n`, so that a secret scalar does not
+    /// influence the timing. The point has to lie in the subgroup of odd
+    /// order `r`.
+    pub fn mul_ct(&self, n: &Zr) -> CurvePoint {
+        let target_field = self.field.target_field();
+        let zero = ZrField::zero_element(Arc::clone(&target_field));
+        let one = ZrField::one_element(target_field);
+        let a = self.field.a();
+        let b3 = self.field.b().double() + self.field.b();
+
+        // invariant: r1 = r0 + self
+        let mut r0 = Projective { x: zero.clone(), y: one.clone(), z: zero };
+        let mut r1 = if self.infinity {
+            r0.clone()
+        } else {
+            Projective { x: self.x.clone(), y: self.y.clone(), z: one }
+        };
+        let n = Wiped(n.to_limbs());
+        for i in (0..64 * n.len()).rev() {
+            let bit = Choice::from(((n[i / 64] >> (i % 64)) & 1) as u8);
+            Projective::conditional_swap(&mut r0, &mut r1, bit);
+            r1 = r0.add(&r1, a, &b3);
+            r0 = r0.add(&r0, a, &b3);
+            Projective::conditional_swap(&mut r0, &mut r1, bit);
+        }
+
+        match Option::<Zr>::from(r0.z.invert_ct()) {
+            Some(z_inverse) => CurvePoint::new(r0.x * &z_inverse, r0.y * &z_inverse, Arc::clone(&self.field)),
+            None => CurvePoint::infinity(Arc::clone(&self.field)),
+        }
+    }
+
+    /// adds two points of the same curve
+    fn add_point(&self, other: &CurvePoint) -> CurvePoint {
+        if self.infinity {
+            return other.clone();
+        }
+        if other.infinity {
+            return self.clone();
+        }
+
+        if self.x == other.x {
+            if self.y == other.y {
+                return self.double();
+            }
+            // P + (-P)
+            return CurvePoint::infinity(Arc::clone(&self.field));
+        }
+
+        let lambda = (other.y.clone() - &self.y) / (other.x.clone() - &self.x);
+        let x = lambda.square() - &self.x - &other.x;
+        let y = lambda * (self.x.clone() - &x) - &self.y;
+        CurvePoint::new(x, y, Arc::clone(&self.field))
+    }
+
+    fn double_point(&self) -> CurvePoint {
+        if self.infinity || self.y.is_zero() {
+            return CurvePoint::infinity(Arc::clone(&self.field));
+        }
+
+        let numerator = self.x.square() * Zr::new(Integer::from(3), self.x.field()) + self.field.a();
+        let lambda = numerator / self.y.double();
+        let x = lambda.square() - &self.x.double();
+        let y = lambda * (self.x.clone() - &x) - &self.y;
+        CurvePoint::new(x, y, Arc::clone(&self.field))
+    }
+}
+
+/// the point (x/z, y/z), or the point at infinity for z = 0
+#[derive(Clone)]
+struct Projective {
+    x: Zr,
+    y: Zr,
+    z: Zr,
+}
+
+impl Projective {
+    /// the complete addition formulas of Renes, Costello and Batina
+    /// (algorithm 1 of ePrint 2015/1060), which need neither inversions nor
+    /// branches. They have no exceptional cases if the difference of the
+    /// points does not have order two, e.g. for points of odd order.
+    fn add(&self, other: &Projective, a: &Zr, b3: &Zr) -> Projective {
+        let (x1, y1, z1) = (&self.x, &self.y, &self.z);
+        let (x2, y2, z2) = (&other.x, &other.y, &other.z);
+        let t0 = x1 * x2;
+        let t1 = y1 * y2;
+        let t2 = z1 * z2;
+        // x1·y2 + x2·y1, x1·z2 + x2·z1 and y1·z2 + y2·z1
+        let t3 = (x1 + y1) * (x2 + y2) - (&t0 + &t1);
+        let t4 = (x1 + z1) * (x2 + z2) - (&t0 + &t2);
+        let t5 = (y1 + z1) * (y2 + z2) - (&t1 + &t2);
+
+        let z3 = a * &t4 + b3 * &t2;
+        let x3 = &t1 - &z3;
+        let z3 = &t1 + &z3;
+        let y3 = &x3 * &z3;
+        let t1 = t0.double() + &t0;
+        let t2 = a * &t2;
+        let t4 = b3 * &t4 + a * (&t0 - &t2);
+        let t1 = t1 + &t2;
+        Projective {
+            x: &t3 * &x3 - &t5 * &t4,
+            y: y3 + &t1 * &t4,
+            z: &t5 * &z3 + &t3 * &t1,
+        }
+    }
+}
+
+impl ConditionallyAssignable for Projective {
+    fn conditional_assign(&mut self, other: &Self, choice: Choice) {
+        self.x.conditional_assign(&other.x, choice);
+        self.y.conditional_assign(&other.y, choice);
+        self.z.conditional_assign(&other.z, choice);
+    }
+}
+
+impl PartialEq for CurvePoint {
+    fn eq(&self, other: &Self) -> bool {
+        if self.infinity || other.infinity {
+            self.infinity == other.infinity
+        } else {
+            self.x == other.x && self.y == other.y
+        }
+    }
+}
+
+impl SuperElement<Zr, AtomicElement> for CurvePoint {
+    type SubType = Zr;
+}
+
+impl Element<ComplexElement> for CurvePoint {
+    type FieldType = CurveField;
+
+    fn field(&self) -> Arc<Self::FieldType> {
+        Arc::clone(&self.field)
+    }
+
+    fn invert(&self) -> Option<Self> { Some(self.clone().neg()) }
+    fn square(&self) -> Self { self.double_point() }
+    fn double(&self) -> Self { self.double_point() }
+
+    /// only valid for points in the subgroup of order `r`
+    fn halve(&self) -> Self {
+        let order = self.field.order();
+        let two_inverse = BigInteger::invert(&Integer::from(2), order).expect("unable to invert");
+        self.mul_mpz(&two_inverse)
+    }
+
+    /// every point of a group of odd order is a square
+    fn is_sqrt(&self) -> bool { true }
+
+    fn sqrt(&self) -> Option<(Self, Self)> {
+        let root = self.halve();
+        Some((root.clone(), root))
+    }
+
+    fn mul_zn(&self, n: &Zr) -> Self { self.mul_mpz(&Wiped(n.value())) }
+    fn pow(&self, exp: &Integer) -> Self { self.mul_mpz(exp) }
+
+    fn try_add(&self, other: &Self) -> Result<Self, PbcError> {
+        check_same_field(self, other)?;
+        Ok(self.add_point(other))
+    }
+
+    fn try_sub(&self, other: &Self) -> Result<Self, PbcError> {
+        check_same_field(self, other)?;
+        Ok(self.add_point(&other.clone().neg()))
+    }
+
+    fn try_mul(&self, other: &Self) -> Result<Self, PbcError> { self.try_add(other) }
+
+    /// the inverse of a point always exists
+    fn try_div(&self, other: &Self) -> Result<Self, PbcError> { self.try_sub(other) }
+
+    fn try_set(mut self, other: &Self) -> Result<Self, PbcError> {
+        check_same_field(&self, other)?;
+        self.x = other.x.clone();
+        self.y = other.y.clone();
+        self.infinity = other.infinity;
+        Ok(self)
+    }
+}
+
+/// x followed by y, like PBC does. The point at infinity is written as
+/// (0, 0), which is also the encoding of the point (0, 0) if b = 0, as on
+/// the curves of type A. That point has order 2 and is not in the subgroup
+/// of odd order, so zeros always decode to infinity.
+impl ToBytes for CurvePoint {
+    fn length_in_bytes(&self) -> usize {
+        self.field.length_in_bytes()
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.x.to_bytes();
+        bytes.extend(self.y.to_bytes());
+        bytes
+    }
+}
+
+/// only accepts points of the subgroup, which costs a scalar
+/// multiplication with the order of the group
+impl FromBytes<CurveField> for CurvePoint {
+    fn from_bytes(field: Arc<CurveField>, bytes: &[u8]) -> Result<Self, BytesError> {
+        check_length(field.as_ref(), bytes)?;
+        if bytes.iter().all(|b| *b == 0) {
+            return Ok(CurvePoint::infinity(field));
+        }
+
+        let target_field = field.target_field();
+        let (x, y) = bytes.split_at(target_field.length_in_bytes());
+        let x = Zr::from_bytes(Arc::clone(&target_field), x)?;
+        let y = Zr::from_bytes(target_field, y)?;
+        let point = CurvePoint::new(x, y, field);
+        if ! point.is_valid() {
+            return Err(BytesError::NotOnCurve);
+        }
+        point.check_subgroup()
+    }
+}
+
+/// compressed layout of PBC's `element_to_bytes_compressed`, i.e. the x
+/// coordinate followed by a single byte which is 1 if y is odd and 0
+/// otherwise. As in the full layout, infinity is encoded by zeros only.
+impl CurvePoint {
+    pub fn to_bytes_compressed(&self) -> Vec<u8> {
+        let mut bytes = self.x.to_bytes();
+        bytes.push(if self.y.value().tstbit(0) { 1 } else { 0 });
+        bytes
+    }
+
+    /// recovers y from x by taking a square root of x³ + ax + b. Like
+    /// `from_bytes`, it only accepts points of the subgroup.
+    pub fn from_bytes_compressed(field: Arc<CurveField>, bytes: &[u8]) -> Result<Self, BytesError> {
+        let expected = field.length_in_bytes_compressed();
+        if bytes.len() != expected {
+            return Err(BytesError::InvalidLength { expected, actual: bytes.len() });
+        }
+        if bytes.iter().all(|b| *b == 0) {
+            return Ok(CurvePoint::infinity(field));
+        }
+
+        let (x, sign) = bytes.split_at(expected - 1);
+        let odd = match sign[0] {
+            0 => false,
+            1 => true,
+            _ => return Err(BytesError::OutOfRange),
+        };
+        let x = Zr::from_bytes(field.target_field(), x)?;
+        let (y, neg_y) = field.rhs(&x).sqrt().ok_or(BytesError::NotOnCurve)?;
+        // zero has no odd root
+        if odd && y.is_zero() {
+            return Err(BytesError::OutOfRange);
+        }
+        let y = if y.value().tstbit(0) == odd { y } else { neg_y };
+        CurvePoint::new(x, y, field).check_subgroup()
+    }
+
+    /// rejects points whose multiple with the order of the group is not
+    /// infinity, e.g. points of the full curve which an attacker chose
+    fn check_subgroup(self) -> Result<Self, BytesError> {
+        if self.mul_mpz(self.field.order()).is_infinity() {
+            Ok(self)
+        } else {
+            Err(BytesError::NotInSubgroup)
+        }
+    }
+}
+
+impl CanBeZero for CurvePoint {
+    fn is_zero(&self) -> bool { self.infinity }
+}
+
+impl CanBeOne for CurvePoint {
+    fn is_one(&self) -> bool { self.infinity }
+}
+
+impl Set<&CurvePoint> for CurvePoint {
+    fn set(self, other: &Self) -> Self {
+        self.try_set(other).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl Neg for CurvePoint {
+    type Output = CurvePoint;
+    fn neg(mut self) -> Self::Output {
+        if ! self.infinity {
+            self.y = self.y.neg();
+        }
+        self
+    }
+}
+
+macro_rules! add_operators {
+    ($($op:tt $method:ident),+) => {
+        $(
+            impl_op_ex!($op |lhs: &CurvePoint, rhs: &CurvePoint| -> CurvePoint {
+                lhs.$method(rhs).unwrap_or_else(|e| panic!("{}", e))
+            });
+        )+
+    };
+}
+add_operators!(+ try_add, * try_mul, - try_sub, / try_div);
+impl_op_ex!(* |lhs: &CurvePoint, rhs: &Zr| -> CurvePoint { lhs.mul_zn(rhs) });
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ops::*;
+    use crate::pbc::elements::ZrField;
+    use crate::pbc::testlib::algebra::*;
+    use crate::pbc::testlib::curve::type_a_options;
+    use crate::test_one;
+    use crate::test_zero;
+    use crate::test_neg;
+    use crate::test_associativity;
+    use crate::test_commutativity;
+    use crate::test_double_and_halve;
+    use crate::test_pow;
+    use crate::test_invert;
+    use crate::test_bytes;
+    use crate::test_pow_preprocessed;
+    use crate::test_multi_exp;
+
+    fn field() -> Arc<CurveField> { Arc::new(CurveField::type_a(&type_a_options())) }
+
+    test_one!(CurvePoint, CurveField, field());
+    test_zero!(CurvePoint, CurveField, field());
+    test_neg!(CurvePoint, CurveField, field());
+    test_double_and_halve!(CurvePoint, CurveField, field());
+    test_commutativity!(CurvePoint, add, CurveField, field());
+    test_associativity!(CurvePoint, add, CurveField, field());
+    test_pow!(CurvePoint, CurveField, field());
+    test_invert!(CurvePoint, CurveField, field());
+    test_bytes!(CurvePoint, CurveField, field());
+    test_pow_preprocessed!(CurvePoint, CurveField, field());
+    test_multi_exp!(CurvePoint, CurveField, field());
+
+    #[test]
+    fn test_mul_ct() {
+        let field = field();
+        let zr_field = Arc::new(ZrField::new(field.order().clone()));
+        let p = CurveField::random_element(Arc::clone(&field));
+        let scalars = [
+            ZrField::random_element(Arc::clone(&zr_field)),
+            ZrField::zero_element(Arc::clone(&zr_field)),
+            ZrField::one_element(Arc::clone(&zr_field)),
+            -ZrField::one_element(Arc::clone(&zr_field)),
+        ];
+        for n in &scalars {
+            assert_eq!(p.mul_ct(n), p.mul_zn(n));
+        }
+        assert!(p.mul_ct(&scalars[1]).is_infinity());
+        assert!(CurvePoint::infinity(field).mul_ct(&scalars[0]).is_infinity());
+    }
+
+    #[test]
+    fn test_random_point_has_order_r() {
+        let field = field();
+        let p = CurveField::random_element(Arc::clone(&field));
+        assert!(p.is_valid());
+        assert!(! p.is_infinity());
+        assert!(p.mul_mpz(field.order()).is_infinity());
+    }
+
+    #[test]
+    fn test_mul_zn() {
+        let field = field();
+        let zr_field = Arc::new(ZrField::new(field.order().clone()));
+        let p = CurveField::random_element(Arc::clone(&field));
+        let a = ZrField::random_element(Arc::clone(&zr_field));
+        let b = ZrField::random_element(zr_field);
+
+        assert_eq!(p.mul_zn(&a) + p.mul_zn(&b), p.mul_zn(&(&a + &b)));
+        assert_eq!(p.mul_zn(&a).mul_zn(&b), &p * &(a * b));
+        assert_eq!(p.double(), &p + &p);
+    }
+
+    #[test]
+    fn test_bytes_of_special_points() {
+        let field = field();
+        let infinity = CurveField::zero_element(Arc::clone(&field));
+        let bytes = infinity.to_bytes();
+        assert!(bytes.iter().all(|b| *b == 0));
+        assert_eq!(CurvePoint::from_bytes(Arc::clone(&field), &bytes), Ok(infinity));
+
+        let p = CurveField::random_element(Arc::clone(&field));
+        let mut bytes = p.to_bytes();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 1;
+        assert_eq!(CurvePoint::from_bytes(field, &bytes), Err(BytesError::NotOnCurve));
+    }
+
+    #[test]
+    fn test_bytes_of_point_of_order_two() {
+        let field = field();
+        let target_field = field.target_field();
+        let zero = Arc::clone(&target_field).zero_element();
+        let point = CurvePoint::new(zero.clone(), zero, Arc::clone(&field));
+        assert!(point.is_valid());
+        assert!(point.double().is_infinity());
+
+        let infinity = CurveField::zero_element(Arc::clone(&field));
+        assert_eq!(point.to_bytes(), infinity.to_bytes());
+        assert_eq!(CurvePoint::from_bytes(field, &point.to_bytes()), Ok(infinity));
+    }
+
+    #[test]
+    fn test_bytes_compressed() {
+        let field = field();
+        for _ in 0..10 {
+            let p = CurveField::random_element(Arc::clone(&field));
+            let bytes = p.to_bytes_compressed();
+            assert_eq!(bytes.len(), field.length_in_bytes_compressed());
+            assert_eq!(CurvePoint::from_bytes_compressed(Arc::clone(&field), &bytes), Ok(p.clone()));
+
+            let neg_bytes = p.clone().neg().to_bytes_compressed();
+            assert_eq!(bytes[..bytes.len() - 1], neg_bytes[..neg_bytes.len() - 1]);
+            assert_ne!(bytes.last(), neg_bytes.last());
+        }
+
+        let infinity = CurveField::zero_element(Arc::clone(&field));
+        let bytes = infinity.to_bytes_compressed();
+        assert_eq!(CurvePoint::from_bytes_compressed(Arc::clone(&field), &bytes), Ok(infinity));
+        assert_eq!(
+            CurvePoint::from_bytes_compressed(Arc::clone(&field), &bytes[1..]),
+            Err(BytesError::InvalidLength { expected: bytes.len(), actual: bytes.len() - 1 }));
+    }
+
+    #[test]
+    fn test_bytes_compressed_without_sqrt() {
+        let field = field();
+        let target_field = field.target_field();
+        let x = (1..).map(|x| Zr::new(Integer::from(x), Arc::clone(&target_field)))
+            .find(|x| ! field.rhs(x).is_sqrt())
+            .unwrap();
+        let mut bytes = x.to_bytes();
+        bytes.push(0);
+        assert_eq!(CurvePoint::from_bytes_compressed(Arc::clone(&field), &bytes), Err(BytesError::NotOnCurve));
+
+        let last = bytes.len() - 1;
+        bytes[last] = 2;
+        assert_eq!(CurvePoint::from_bytes_compressed(field, &bytes), Err(BytesError::OutOfRange));
+    }
+
+    #[test]
+    fn test_bytes_of_zero_y() {
+        // x = 0 has the only root y = 0, so the odd sign is out of range
+        let field = field();
+        let mut bytes = vec![0; field.length_in_bytes_compressed()];
+        let last = bytes.len() - 1;
+        bytes[last] = 1;
+        assert_eq!(CurvePoint::from_bytes_compressed(field, &bytes), Err(BytesError::OutOfRange));
+    }
+
+    #[test]
+    fn test_bytes_outside_of_subgroup() {
+        let field = field();
+        let target_field = field.target_field();
+        let point = (1..)
+            .map(|x| Zr::new(Integer::from(x), Arc::clone(&target_field)))
+            .filter_map(|x| field.rhs(&x).sqrt().map(|(y, _)| CurvePoint::new(x, y, Arc::clone(&field))))
+            .find(|p| ! p.mul_mpz(field.order()).is_infinity())
+            .unwrap();
+        assert!(point.is_valid());
+        assert_eq!(CurvePoint::from_bytes(Arc::clone(&field), &point.to_bytes()), Err(BytesError::NotInSubgroup));
+        assert_eq!(
+            CurvePoint::from_bytes_compressed(Arc::clone(&field), &point.to_bytes_compressed()),
+            Err(BytesError::NotInSubgroup));
+
+        let point = point.mul_mpz(field.cofactor());
+        assert_eq!(CurvePoint::from_bytes(field, &point.to_bytes()), Ok(point));
+    }
+
+    #[test]
+    fn test_from_hash() {
+        let field = field();
+        let p = Arc::clone(&field).from_hash(b"alice@example.com");
+        assert!(p.is_valid());
+        assert!(! p.is_infinity());
+        assert!(p.mul_mpz(field.order()).is_infinity());
+        assert_eq!(p, Arc::clone(&field).from_hash(b"alice@example.com"));
+        assert_ne!(p, field.from_hash(b"bob@example.com"));
+    }
+
+    #[test]
+    fn test_try_operations() {
+        let field = field();
+        let other_field = Arc::new(CurveField::type_a(&crate::pbc::TypeA1CurveGenerator::new(2, 16).generate()));
+        let p = CurveField::random_element(Arc::clone(&field));
+        let q = CurveField::random_element(Arc::clone(&field));
+        let r = CurveField::random_element(other_field);
+
+        assert_eq!(p.try_add(&q), Ok(&p + &q));
+        assert_eq!(p.try_div(&q), Ok(&p / &q));
+        assert_eq!(p.try_sub(&q), Ok(&p - &q));
+        assert_eq!(p.try_mul(&q), Ok(&p * &q));
+        assert_eq!(p.clone().try_set(&q), Ok(q.clone()));
+        assert_eq!(p.try_add(&r), Err(PbcError::FieldMismatch));
+        assert_eq!(p.try_sub(&r), Err(PbcError::FieldMismatch));
+        assert_eq!(p.try_mul(&r), Err(PbcError::FieldMismatch));
+        assert_eq!(p.try_div(&r), Err(PbcError::FieldMismatch));
+        assert_eq!(p.try_set(&r), Err(PbcError::FieldMismatch));
+    }
+}
@@ -0,0 +1,11 @@
+mod type_a_generator;
+mod type_a1_generator;
+mod curve_field;
+mod curve_point;
+mod param;
+
+pub use type_a_generator::*;
+pub use type_a1_generator::*;
+pub use curve_field::*;
+pub use curve_point::*;
+pub use param::*;
@@ -0,0 +1,334 @@
+use crate::pbc::{BigInteger, Integer, ProbabPrimeResult};
+use super::{CurveOptions, CurveType};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+/// error which occurs while reading a PBC parameter file
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParamError {
+    Io(String),
+    Syntax { line: usize, message: String },
+    UnsupportedType { line: usize, curve_type: String },
+    UnknownKey { line: usize, key: String },
+    DuplicateKey { line: usize, key: String },
+    InvalidValue { line: usize, key: String, value: String },
+    MissingKey(String),
+    Inconsistent { line: usize, message: String },
+}
+
+impl fmt::Display for ParamError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParamError::Io(message) => write!(f, "unable to read parameters: {}", message),
+            ParamError::Syntax { line, message } => write!(f, "line {}: {}", line, message),
+            ParamError::UnsupportedType { line, curve_type } => write!(f, "line {}: unsupported curve type '{}'", line, curve_type),
+            ParamError::UnknownKey { line, key } => write!(f, "line {}: unknown key '{}'", line, key),
+            ParamError::DuplicateKey { line, key } => write!(f, "line {}: duplicate key '{}'", line, key),
+            ParamError::InvalidValue { line, key, value } => write!(f, "line {}: invalid value '{}' for '{}'", line, value, key),
+            ParamError::MissingKey(key) => write!(f, "missing key '{}'", key),
+            ParamError::Inconsistent { line, message } => write!(f, "line {}: {}", line, message),
+        }
+    }
+}
+
+impl std::error::Error for ParamError {}
+
+impl fmt::Display for CurveType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CurveType::A => write!(f, "a"),
+            CurveType::A1 => write!(f, "a1"),
+        }
+    }
+}
+
+const TYPE_A_KEYS: [&str; 7] = ["q", "h", "r", "exp2", "exp1", "sign1", "sign0"];
+const TYPE_A1_KEYS: [&str; 3] = ["p", "n", "l"];
+
+/// writes the parameters in the format of `pbc_param_out_str`
+impl fmt::Display for CurveOptions {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "type {}", self.curve_type)?;
+        if self.curve_type == CurveType::A1 {
+            writeln!(f, "p {}", self.q)?;
+            writeln!(f, "n {}", self.r)?;
+            return writeln!(f, "l {}", self.h);
+        }
+        writeln!(f, "q {}", self.q)?;
+        writeln!(f, "h {}", self.h)?;
+        writeln!(f, "r {}", self.r)?;
+        writeln!(f, "exp2 {}", self.exp2)?;
+        writeln!(f, "exp1 {}", self.exp1)?;
+        writeln!(f, "sign1 {}", self.sign1)?;
+        writeln!(f, "sign0 {}", self.sign0)
+    }
+}
+
+/// reads parameters in the format of `pbc_param_init_set_str`. Comments
+/// start with `#` and extend to the end of the line.
+impl FromStr for CurveOptions {
+    type Err = ParamError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut curve_type = None;
+        let mut values: HashMap<&str, (usize, &str)> = HashMap::new();
+
+        for (index, line) in s.lines().enumerate() {
+            let line_number = index + 1;
+            let content = match line.find('#') {
+                Some(pos) => &line[..pos],
+                None => line,
+            };
+            let tokens: Vec<&str> = content.split_whitespace().collect();
+            let (key, value) = match tokens.as_slice() {
+                [] => continue,
+                [key, value] => (*key, *value),
+                [key] => return Err(ParamError::Syntax {
+                    line: line_number,
+                    message: format!("missing value for '{}'", key),
+                }),
+                _ => return Err(ParamError::Syntax {
+                    line: line_number,
+                    message: "expected a key and a single value".to_string(),
+                }),
+            };
+
+            if key == "type" {
+                if curve_type.is_some() {
+                    return Err(ParamError::DuplicateKey { line: line_number, key: key.to_string() });
+                }
+                curve_type = match value {
+                    "a" => Some(CurveType::A),
+                    "a1" => Some(CurveType::A1),
+                    _ => return Err(ParamError::UnsupportedType {
+                        line: line_number,
+                        curve_type: value.to_string(),
+                    }),
+                };
+            } else if values.insert(key, (line_number, value)).is_some() {
+                return Err(ParamError::DuplicateKey { line: line_number, key: key.to_string() });
+            }
+        }
+
+        let curve_type = curve_type.ok_or_else(|| ParamError::MissingKey("type".to_string()))?;
+        let keys: &[&str] = match curve_type {
+            CurveType::A => &TYPE_A_KEYS,
+            CurveType::A1 => &TYPE_A1_KEYS,
+        };
+        let unknown_key = values.iter()
+            .filter(|(key, _)| ! keys.contains(key))
+            .min_by_key(|(_, (line, _))| *line);
+        if let Some((key, (line, _))) = unknown_key {
+            return Err(ParamError::UnknownKey { line: *line, key: key.to_string() });
+        }
+
+        let parser = ParamParser { values };
+        let options = match curve_type {
+            CurveType::A => CurveOptions {
+                curve_type,
+                q: parser.mpz("q")?,
+                h: parser.mpz("h")?,
+                r: parser.mpz("r")?,
+                exp1: parser.number("exp1")?,
+                exp2: parser.number("exp2")?,
+                sign0: parser.sign("sign0")?,
+                sign1: parser.sign("sign1")?,
+            },
+            CurveType::A1 => CurveOptions {
+                curve_type,
+                q: parser.mpz("p")?,
+                h: parser.mpz("l")?,
+                r: parser.mpz("n")?,
+                exp1: 0,
+                exp2: 0,
+                sign0: 0,
+                sign1: 0,
+            },
+        };
+        parser.validate(&options)?;
+        Ok(options)
+    }
+}
+
+impl CurveOptions {
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<CurveOptions, ParamError> {
+        let content = std::fs::read_to_string(path).map_err(|why| ParamError::Io(why.to_string()))?;
+        content.parse()
+    }
+
+    pub fn to_file<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        std::fs::write(path, self.to_string())
+    }
+}
+
+struct ParamParser<'a> {
+    values: HashMap<&'a str, (usize, &'a str)>,
+}
+
+impl<'a> ParamParser<'a> {
+    fn get(&self, key: &str) -> Result<(usize, &'a str), ParamError> {
+        self.values.get(key).copied().ok_or_else(|| ParamError::MissingKey(key.to_string()))
+    }
+
+    fn line(&self, key: &str) -> usize {
+        self.values.get(key).map(|(line, _)| *line).unwrap_or(0)
+    }
+
+    fn invalid_value(key: &str, line: usize, value: &str) -> ParamError {
+        ParamError::InvalidValue { line, key: key.to_string(), value: value.to_string() }
+    }
+
+    fn mpz(&self, key: &str) -> Result<Integer, ParamError> {
+        let (line, value) = self.get(key)?;
+        match Integer::from_str_radix(value, 10) {
+            Ok(number) if number > Integer::zero() => Ok(number),
+            _ => Err(Self::invalid_value(key, line, value)),
+        }
+    }
+
+    fn number(&self, key: &str) -> Result<u32, ParamError> {
+        let (line, value) = self.get(key)?;
+        value.parse().map_err(|_| Self::invalid_value(key, line, value))
+    }
+
+    fn sign(&self, key: &str) -> Result<i32, ParamError> {
+        let (line, value) = self.get(key)?;
+        match value {
+            "1" => Ok(1),
+            "-1" => Ok(-1),
+            _ => Err(Self::invalid_value(key, line, value)),
+        }
+    }
+
+    fn validate(&self, options: &CurveOptions) -> Result<(), ParamError> {
+        let inconsistent = |key: &str, message: &str| ParamError::Inconsistent {
+            line: self.line(key),
+            message: message.to_string(),
+        };
+
+        if options.curve_type == CurveType::A1 {
+            if &options.q + Integer::one() != &options.h * &options.r {
+                return Err(inconsistent("p", "p + 1 does not equal l * n"));
+            }
+            if ! options.h.modulus(&Integer::from(4)).is_zero() {
+                return Err(inconsistent("l", "l is not a multiple of 4"));
+            }
+            if BigInteger::probab_prime(&options.q, 10) == ProbabPrimeResult::NotPrime {
+                return Err(inconsistent("p", "p is not prime"));
+            }
+            return Ok(());
+        }
+
+        if options.exp1 == 0 || options.exp1 >= options.exp2 {
+            return Err(inconsistent("exp1", "exp1 must be between 0 and exp2"));
+        }
+
+        let mut r = Integer::zero();
+        r.setbit(options.exp2 as usize);
+        let mut r1 = Integer::zero();
+        r1.setbit(options.exp1 as usize);
+        let r = r + r1 * Integer::from(options.sign1) + Integer::from(options.sign0);
+        if r != options.r {
+            return Err(inconsistent("r", "r does not equal 2^exp2 + sign1 * 2^exp1 + sign0"));
+        }
+        if BigInteger::probab_prime(&options.r, 10) == ProbabPrimeResult::NotPrime {
+            return Err(inconsistent("r", "r is not prime"));
+        }
+
+        if &options.q + Integer::one() != &options.h * &options.r {
+            return Err(inconsistent("q", "q + 1 does not equal h * r"));
+        }
+        if options.q.modulus(&Integer::from(4)) != Integer::from(3) {
+            return Err(inconsistent("q", "q is not congruent to 3 mod 4"));
+        }
+        if BigInteger::probab_prime(&options.q, 10) == ProbabPrimeResult::NotPrime {
+            return Err(inconsistent("q", "q is not prime"));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pbc::{TypeACurveGenerator, TypeA1CurveGenerator};
+
+    /// `param/a.param` of the PBC distribution
+    const A_PARAM: &str = "type a
+q 8780710799663312522437781984754049815806883199414208211028653399266475630880222957078625179422662221423155858769582317459277713367317481324925129998224791
+h 12016012264891146079388821366740534204802954401251311822919615131047207289359704531102844802183906537786776
+r 730750818665451621361119245571504901405976559617
+exp2 159
+exp1 107
+sign1 1
+sign0 1
+";
+
+    #[test]
+    fn test_parse_pbc_param() {
+        let options: CurveOptions = A_PARAM.parse().unwrap();
+        assert_eq!(options.curve_type, CurveType::A);
+        assert_eq!(options.exp2, 159);
+        assert_eq!(options.exp1, 107);
+        assert_eq!(options.sign1, 1);
+        assert_eq!(options.sign0, 1);
+        assert_eq!(options.r, Integer::from_str_radix("730750818665451621361119245571504901405976559617", 10).unwrap());
+        assert_eq!(options.to_string(), A_PARAM);
+    }
+
+    #[test]
+    fn test_write_generated_param() {
+        let options = TypeACurveGenerator::new(32, 64).generate();
+        let text = options.to_string();
+        let parsed: CurveOptions = text.parse().unwrap();
+        assert_eq!(parsed, options);
+        assert_eq!(parsed.to_string(), text);
+    }
+
+    #[test]
+    fn test_type_a1_param() {
+        let options = TypeA1CurveGenerator::new(2, 32).generate();
+        let text = options.to_string();
+        assert!(text.starts_with(&format!("type a1\np {}\nn {}\nl {}\n", options.q, options.r, options.h)));
+        assert_eq!(text.lines().count(), 4);
+
+        let parsed: CurveOptions = text.parse().unwrap();
+        assert_eq!(parsed, options);
+
+        let error = text.replace("type a1", "type a").parse::<CurveOptions>().unwrap_err();
+        assert_eq!(error, ParamError::UnknownKey { line: 2, key: "p".to_string() });
+    }
+
+    #[test]
+    fn test_comments_and_whitespace() {
+        let text = format!("# generated by PBC\n\n{}", A_PARAM.replace("exp2 159", "  exp2\t159   # Solinas"));
+        let options: CurveOptions = text.parse().unwrap();
+        assert_eq!(options.to_string(), A_PARAM);
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = A_PARAM.replace("type a", "type d").parse::<CurveOptions>().unwrap_err();
+        assert_eq!(error, ParamError::UnsupportedType { line: 1, curve_type: "d".to_string() });
+
+        let error = A_PARAM.replace("exp1 107", "exp1 x").parse::<CurveOptions>().unwrap_err();
+        assert_eq!(error, ParamError::InvalidValue { line: 6, key: "exp1".to_string(), value: "x".to_string() });
+
+        let error = A_PARAM.replace("sign1 1", "sign1").parse::<CurveOptions>().unwrap_err();
+        assert!(matches!(error, ParamError::Syntax { line: 7, .. }));
+
+        let error = A_PARAM.replace("sign0 1", "sign2 1").parse::<CurveOptions>().unwrap_err();
+        assert_eq!(error, ParamError::UnknownKey { line: 8, key: "sign2".to_string() });
+
+        let error = A_PARAM.replace("sign0 1\n", "").parse::<CurveOptions>().unwrap_err();
+        assert_eq!(error, ParamError::MissingKey("sign0".to_string()));
+
+        let error = A_PARAM.replace("sign0 1", "sign0 -1").parse::<CurveOptions>().unwrap_err();
+        assert!(matches!(error, ParamError::Inconsistent { line: 4, .. }));
+
+        let error = format!("{}q 7\n", A_PARAM).parse::<CurveOptions>().unwrap_err();
+        assert_eq!(error, ParamError::DuplicateKey { line: 9, key: "q".to_string() });
+    }
+}
@@ -0,0 +1,129 @@
+use crate::pbc::{BigInteger, Integer, ProbabPrimeResult};
+use rand::*;
+use super::{CurveOptions, CurveType};
+
+/// generates Type A1 curves, i.e. y² = x³ + x over F_q with a group of
+/// composite order n = p1·…·pk, as `pbc_param_init_a1_gen` does.
+///
+/// The prime field order is stored in `q`, the group order in `r` and the
+/// cofactor l = (q + 1) / n in `h`.
+pub struct TypeA1CurveGenerator {
+    primes: usize,
+    bits: u32,
+}
+
+impl TypeA1CurveGenerator {
+    pub fn new(primes: usize, bits: u32) -> Self {
+        assert!(primes > 0);
+        assert!(bits > 2);
+
+        Self { primes, bits }
+    }
+
+    pub fn generate(&self) -> CurveOptions {
+        self.generate_with(&mut rand::thread_rng())
+    }
+
+    /// generates a curve using `rng` as the only source of randomness, so
+    /// that a seeded rng always leads to the same curve
+    pub fn generate_with<R: RngCore + CryptoRng>(&self, rng: &mut R) -> CurveOptions {
+        self.generate_with_primes_from(rng).0
+    }
+
+    /// generates a curve and returns the prime factors of its order, which
+    /// are required to find generators of the subgroups
+    pub fn generate_with_primes(&self) -> (CurveOptions, Vec<Integer>) {
+        self.generate_with_primes_from(&mut rand::thread_rng())
+    }
+
+    /// like `generate_with_primes`, but with `rng` as the only source of
+    /// randomness
+    pub fn generate_with_primes_from<R: RngCore + CryptoRng>(&self, rng: &mut R) -> (CurveOptions, Vec<Integer>) {
+        let mut primes: Vec<Integer> = Vec::with_capacity(self.primes);
+        while primes.len() < self.primes {
+            let prime = self.next_prime(rng);
+            if ! primes.contains(&prime) {
+                primes.push(prime);
+            }
+        }
+        let n = primes.iter().fold(Integer::one(), |n, p| n * p);
+
+        // find the smallest l ≡ 0 mod 4, so that q = l·n - 1 is prime
+        let mut l = Integer::from(4);
+        let step = &n * Integer::from(4);
+        let mut q = &l * &n - Integer::one();
+        while BigInteger::probab_prime(&q, 20) == ProbabPrimeResult::NotPrime {
+            q += &step;
+            l += 4;
+        }
+
+        let options = CurveOptions {
+            curve_type: CurveType::A1,
+            q,
+            h: l,
+            r: n,
+            exp1: 0,
+            exp2: 0,
+            sign0: 0,
+            sign1: 0,
+        };
+        (options, primes)
+    }
+
+    /// returns a random prime of exactly `bits` bits
+    fn next_prime<R: RngCore>(&self, rng: &mut R) -> Integer {
+        loop {
+            let mut max = Integer::zero();
+            max.setbit((self.bits - 1) as usize);
+            let mut prime = Integer::urandom(rng, &max);
+            prime.setbit((self.bits - 1) as usize);
+            let prime = prime.nextprime();
+            if prime.bit_length() == self.bits as usize {
+                return prime;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pbc::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_generate_type_a1() {
+        let (options, primes) = TypeA1CurveGenerator::new(3, 24).generate_with_primes();
+        assert_eq!(options.curve_type, CurveType::A1);
+        assert_eq!(primes.len(), 3);
+        for p in primes.iter() {
+            assert_eq!(p.bit_length(), 24);
+            assert!(BigInteger::probab_prime(p, 20) != ProbabPrimeResult::NotPrime);
+        }
+        assert_eq!(options.r, &(&primes[0] * &primes[1]) * &primes[2]);
+        assert!(BigInteger::probab_prime(&options.q, 20) != ProbabPrimeResult::NotPrime);
+        assert_eq!(&options.q + Integer::one(), &options.h * &options.r);
+        assert!(options.h.modulus(&Integer::from(4)).is_zero());
+
+        let field = Arc::new(CurveField::type_a(&options));
+        let p = CurveField::random_element(Arc::clone(&field));
+        assert!(p.is_valid());
+        assert!(p.mul_mpz(&options.r).is_infinity());
+
+        // the subgroup of order p1 is generated by (n / p1)·P
+        let cofactor = &options.r / &primes[0];
+        let g = p.mul_mpz(&cofactor);
+        assert!(g.mul_mpz(&primes[0]).is_infinity());
+    }
+
+    #[test]
+    fn test_generate_type_a1_with_seeded_rng() {
+        use rand::rngs::StdRng;
+
+        let generator = TypeA1CurveGenerator::new(2, 24);
+        let (options, primes) = generator.generate_with_primes_from(&mut StdRng::seed_from_u64(7));
+        assert_eq!(options, generator.generate_with(&mut StdRng::seed_from_u64(7)));
+        assert_eq!(options.r, &primes[0] * &primes[1]);
+        assert_ne!(options, generator.generate_with(&mut StdRng::seed_from_u64(8)));
+    }
+}
@@ -0,0 +1,181 @@
+use crate::pbc::{BigInteger, Integer, ProbabPrimeResult};
+use rand::*;
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CurveType {
+    A,
+    A1,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CurveOptions {
+    pub curve_type: CurveType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::pbc::elements::serialization::mpz_string"))]
+    pub q: Integer,
+    #[cfg_attr(feature = "serde", serde(with = "crate::pbc::elements::serialization::mpz_string"))]
+    pub h: Integer,
+    #[cfg_attr(feature = "serde", serde(with = "crate::pbc::elements::serialization::mpz_string"))]
+    pub r: Integer,
+
+    pub exp1: u32,
+    pub exp2: u32,
+    pub sign0: i32,
+    pub sign1: i32
+}
+
+pub struct TypeACurveGenerator {
+    rbits: u32,
+    qbits: u32,
+}
+
+impl TypeACurveGenerator {
+    /// generates curves with a group order r of `rbits` bits and a prime q
+    /// of about `qbits` bits. Panics unless rbits ≥ 3, so that r has two
+    /// exponents 0 < exp1 < exp2, and qbits ≥ rbits + 4, so that the
+    /// cofactor h has at least one random bit: its bound
+    /// 2^(qbits - rbits - 3) underflows for smaller qbits, and h is always
+    /// zero for qbits = rbits + 3.
+    pub fn new(rbits: u32, qbits: u32) -> Self {
+        assert!(rbits >= 3, "rbits must be at least 3, but it is {}", rbits);
+        assert!(qbits >= rbits.saturating_add(4), "qbits must be at least rbits + 4, but it is {}", qbits);
+        Self { rbits, qbits }
+    }
+
+    pub fn generate(&self) -> CurveOptions {
+        self.generate_with(&mut rand::thread_rng())
+    }
+
+    /// generates a curve using `rng` as the only source of randomness, so
+    /// that a seeded rng always leads to the same curve
+    pub fn generate_with<R: RngCore + CryptoRng>(&self, rng: &mut R) -> CurveOptions {
+        let mut exp1;
+        let mut exp2;
+        let mut sign0;
+        let mut sign1;
+        let mut h: Integer;
+
+        loop {
+            // r is picked to be a Solinas prime, that is,
+            // r has the form 2a +- 2b +- 1 for some integers 0 < b < a.
+            let mut r = Integer::zero();
+            if !rng.next_u32().is_multiple_of(2) {
+                exp2 = self.rbits - 1;
+                sign1 = 1;
+            } else {
+                exp2 = self.rbits;
+                sign1 = -1;
+            }
+            r.setbit(exp2 as usize);
+
+            let mut q = Integer::zero();
+            exp1 = rng.gen_range(1..exp2);
+            q.setbit(exp1 as usize);
+
+            if sign1 > 0 {
+                r += q;
+            } else {
+                r -= q;
+            }
+
+            if !rng.next_u32().is_multiple_of(2) {
+                sign0 = 1;
+                r += Integer::one();
+            } else {
+                sign0 = -1;
+                r -= 1;
+            }
+
+            // `probab_prime` only answers `Prime` for candidates which trial
+            // division decides, and `Probably` for all others, so only
+            // `NotPrime` rejects a candidate
+            if BigInteger::probab_prime(&r, 20) == ProbabPrimeResult::NotPrime {
+                continue;
+            }
+
+            for _ in 1..10 {
+                // like PBC, h is 12 times a random number below
+                // 2^(qbits - rbits - 3), so that q = h·r - 1 has about qbits
+                // bits. An earlier bound of min(3, …) kept h below 96 for all
+                // sizes.
+                q = Integer::zero();
+                q.setbit((self.qbits - self.rbits - 3) as usize);
+
+                h = Integer::urandom(rng, &q) * Integer::from(12);
+                q = (&h * &r) - Integer::one();
+                if BigInteger::probab_prime(&q, 20) != ProbabPrimeResult::NotPrime {
+                    return CurveOptions {
+                        curve_type: CurveType::A,
+                        h,
+                        q,
+                        r,
+                        exp1,
+                        exp2,
+                        sign0,
+                        sign1,
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pbc::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_generate_type_a() {
+        let options = TypeACurveGenerator::new(32, 64).generate();
+        assert!(BigInteger::probab_prime(&options.r, 20) != ProbabPrimeResult::NotPrime);
+        assert!(BigInteger::probab_prime(&options.q, 20) != ProbabPrimeResult::NotPrime);
+        assert_eq!(&options.q + Integer::one(), &options.h * &options.r);
+
+        let mut r = Integer::zero();
+        r.setbit(options.exp2 as usize);
+        let mut r1 = Integer::zero();
+        r1.setbit(options.exp1 as usize);
+        let r = r + r1 * Integer::from(options.sign1) + Integer::from(options.sign0);
+        assert_eq!(r, options.r);
+
+        let field = Arc::new(CurveField::type_a(&options));
+        let p = CurveField::random_element(Arc::clone(&field));
+        assert!(p.is_valid());
+        assert!(p.mul_mpz(&options.r).is_infinity());
+    }
+
+    #[test]
+    fn test_generate_type_a_with_small_cofactor() {
+        let options = TypeACurveGenerator::new(16, 20).generate();
+        assert_eq!(options.h, Integer::from(12));
+        assert_eq!(&options.q + Integer::one(), &options.h * &options.r);
+    }
+
+    #[test]
+    #[should_panic(expected = "qbits must be at least rbits + 4")]
+    fn test_new_with_small_qbits() {
+        TypeACurveGenerator::new(160, 163);
+    }
+
+    #[test]
+    #[should_panic(expected = "rbits must be at least 3")]
+    fn test_new_with_small_rbits() {
+        TypeACurveGenerator::new(2, 64);
+    }
+
+    #[test]
+    fn test_generate_type_a_with_seeded_rng() {
+        use rand::rngs::StdRng;
+
+        let generator = TypeACurveGenerator::new(32, 64);
+        let options = generator.generate_with(&mut StdRng::seed_from_u64(7));
+        assert_eq!(options, generator.generate_with(&mut StdRng::seed_from_u64(7)));
+
+        let field = Arc::new(CurveField::type_a(&options));
+        let p = CurveField::random_element_with(Arc::clone(&field), &mut StdRng::seed_from_u64(7));
+        assert_eq!(p, CurveField::random_element_with(field, &mut StdRng::seed_from_u64(7)));
+    }
+}
@@ -1,3 +0,0 @@
-use gmp::mpz::Mpz;
-
-trait Element : Clone + From<Mpz> {}
\ No newline at end of file
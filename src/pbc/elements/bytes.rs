@@ -0,0 +1,53 @@
+use std::fmt;
+use std::sync::Arc;
+
+/// error which occurs if an element cannot be read from bytes
+#[derive(Debug, Clone, PartialEq)]
+pub enum BytesError {
+    InvalidLength { expected: usize, actual: usize },
+    /// the encoded value is not less than the order of the field
+    OutOfRange,
+    /// the encoded coordinates do not belong to a point on the curve
+    NotOnCurve,
+    /// the encoded point is on the curve, but its order is not the order
+    /// of the group, i.e. the group order multiple is not infinity
+    NotInSubgroup,
+}
+
+impl fmt::Display for BytesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BytesError::InvalidLength { expected, actual } => write!(f, "expected {} bytes, but got {}", expected, actual),
+            BytesError::OutOfRange => write!(f, "value is out of range"),
+            BytesError::NotOnCurve => write!(f, "point is not on the curve"),
+            BytesError::NotInSubgroup => write!(f, "point is not in the subgroup of the curve"),
+        }
+    }
+}
+
+impl std::error::Error for BytesError {}
+
+/// fields whose elements have a binary representation of fixed size
+pub trait ByteLength {
+    fn length_in_bytes(&self) -> usize;
+}
+
+/// conversion to the layout of PBC's `element_to_bytes`
+pub trait ToBytes {
+    fn length_in_bytes(&self) -> usize;
+    fn to_bytes(&self) -> Vec<u8>;
+}
+
+/// conversion from the layout of PBC's `element_from_bytes`. In contrast to
+/// PBC, invalid values are rejected instead of being reduced.
+pub trait FromBytes<F: ByteLength>: Sized {
+    fn from_bytes(field: Arc<F>, bytes: &[u8]) -> Result<Self, BytesError>;
+}
+
+pub(crate) fn check_length<F: ByteLength>(field: &F, bytes: &[u8]) -> Result<(), BytesError> {
+    let expected = field.length_in_bytes();
+    if bytes.len() != expected {
+        return Err(BytesError::InvalidLength { expected, actual: bytes.len() });
+    }
+    Ok(())
+}
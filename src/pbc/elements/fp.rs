@@ -0,0 +1,544 @@
+use crate::pbc::{BigInteger, Integer};
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::*;
+use std::sync::Arc;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+use super::limbs;
+use super::traits::*;
+use super::{FpField, Wiped, Zr, BytesError, ToBytes, FromBytes, check_length};
+use crate::pbc::PbcError;
+
+/// the parameters of a prime field whose modulus is known at compile time,
+/// see `fp_field!`
+pub trait FieldParams<const LIMBS: usize>: 'static + fmt::Debug + Copy + PartialEq + Send + Sync {
+    /// an odd prime as decimal or hexadecimal (with prefix `0x`) number,
+    /// which must fit into `LIMBS` 64 bit words
+    const MODULUS: &'static str;
+}
+
+/// element of a prime field with a modulus that is known at compile time.
+/// The value is stored in Montgomery representation in `LIMBS` words on
+/// the stack, and the element does not refer to its field, so that the
+/// arithmetic never allocates.
+///
+/// Addition, subtraction, multiplication, `==`, `pow_ct` and `invert_ct`
+/// run in constant time. `sqrt`, `pow` and the conversions from and to
+/// `Integer` are not constant time.
+#[derive(Clone, Copy)]
+pub struct Fp<const LIMBS: usize, P: FieldParams<LIMBS>> {
+    pub(super) limbs: [u64; LIMBS],
+    params: PhantomData<P>,
+}
+
+/// constants, which are evaluated at compile time
+impl<const LIMBS: usize, P: FieldParams<LIMBS>> Fp<LIMBS, P> {
+    const MODULUS: [u64; LIMBS] = limbs::parse(P::MODULUS);
+    const IS_ODD: () = assert!(Self::MODULUS[0] & 1 == 1, "the modulus must be odd");
+    const BITS: usize = limbs::bit_length(&Self::MODULUS);
+    const Q_INV_NEG: u64 = limbs::q_inv_neg(Self::MODULUS[0]);
+    /// R mod q, which is the representation of one
+    const R: [u64; LIMBS] = limbs::pow2_mod(64 * LIMBS, &Self::MODULUS);
+    /// R² mod q, which is used to convert into the representation
+    const R2: [u64; LIMBS] = limbs::pow2_mod(128 * LIMBS, &Self::MODULUS);
+    /// the representation of 2^64
+    const WORD: [u64; LIMBS] = limbs::pow2_mod(64 * LIMBS + 64, &Self::MODULUS);
+    /// q - 1 = ODD·2^TWO_ADICITY
+    const TWO_ADICITY: usize = limbs::trailing_zeros(&limbs::sub(&Self::MODULUS, &limbs::small(1)));
+    const ODD: [u64; LIMBS] = limbs::shr(&limbs::sub(&Self::MODULUS, &limbs::small(1)), Self::TWO_ADICITY);
+    const HALF_ORDER: [u64; LIMBS] = limbs::shr(&limbs::sub(&Self::MODULUS, &limbs::small(1)), 1);
+    const ORDER_MINUS_TWO: [u64; LIMBS] = limbs::sub(&Self::MODULUS, &limbs::small(2));
+    /// the representation of the smallest quadratic nonresidue. The search
+    /// is bounded, because composite moduli, e.g. squares, may have none;
+    /// the smallest nonresidue of a prime of up to 1024 bits is far below
+    /// the bound.
+    const NQR: [u64; LIMBS] = {
+        let mut n = 2;
+        loop {
+            match limbs::jacobi_small(n, &Self::MODULUS) {
+                -1 => break,
+                0 => panic!("the modulus is not a prime, it has a small factor"),
+                _ => n += 1,
+            }
+            assert!(n < 1 << 16, "the modulus is not a prime, it has no small quadratic nonresidue");
+        }
+        Self::from_small(n).limbs
+    };
+}
+
+impl<const LIMBS: usize, P: FieldParams<LIMBS>> Fp<LIMBS, P> {
+    /// reduces `value` modulo the order of the field
+    pub fn new(value: &Integer) -> Self {
+        let digits = Wiped(Wiped(value.modulus(&Self::modulus())).to_u64_digits());
+        let mut canonical = [0; LIMBS];
+        canonical[..digits.len()].copy_from_slice(&digits);
+        Self::from_canonical(canonical)
+    }
+
+    /// the value in [0, order), converted from the Montgomery representation
+    pub fn value(&self) -> Integer {
+        Integer::from_u64_digits(&self.to_limbs())
+    }
+
+    pub fn zero() -> Self { Self::from_limbs([0; LIMBS]) }
+
+    pub fn one() -> Self { Self::from_limbs(Self::R) }
+
+    pub fn modulus() -> Integer {
+        Integer::from_u64_digits(&Self::MODULUS)
+    }
+
+    /// the smallest quadratic nonresidue, which is computed at compile time
+    pub fn nqr() -> Self { Self::from_limbs(Self::NQR) }
+
+    const fn from_limbs(limbs: [u64; LIMBS]) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::IS_ODD;
+        Fp { limbs, params: PhantomData }
+    }
+
+    /// converts a value below 2^64 into the Montgomery representation,
+    /// which reduces values that are not below the modulus
+    const fn from_small(value: u64) -> Self {
+        Self::from_limbs(limbs::mont_mul(&limbs::small(value), &Self::R2, &Self::MODULUS, Self::Q_INV_NEG))
+    }
+
+    /// reduces the little-endian words `value` of any length with Horner's
+    /// method, which performs the same operations for all values
+    fn from_words(value: &[u64]) -> Self {
+        let word = Self::from_limbs(Self::WORD);
+        value.iter().rev().fold(Self::zero(), |acc, w| acc * word + Self::from_small(*w))
+    }
+
+    /// converts a value below the modulus into the Montgomery representation
+    pub(crate) fn from_canonical(value: [u64; LIMBS]) -> Self {
+        Self::from_limbs(limbs::mont_mul(&value, &Self::R2, &Self::MODULUS, Self::Q_INV_NEG))
+    }
+
+    /// the value in [0, order) as little-endian words
+    pub fn to_limbs(&self) -> [u64; LIMBS] {
+        limbs::mont_mul(&self.limbs, &limbs::small(1), &Self::MODULUS, Self::Q_INV_NEG)
+    }
+
+    /// whether `value` is below the modulus
+    pub(crate) fn is_canonical(value: &[u64; LIMBS]) -> bool {
+        limbs::less_than(value, &Self::MODULUS)
+    }
+
+    pub(crate) fn bits() -> usize { Self::BITS }
+
+    /// sliding windows over the bits of the little-endian words `exp`
+    fn pow_limbs(&self, exp: &[u64]) -> Self {
+        Self::from_limbs(limbs::mont_pow(&self.limbs, exp, &Self::R, &Self::MODULUS, Self::Q_INV_NEG))
+    }
+
+    /// calculates `self^exp` with a Montgomery ladder, which performs the
+    /// same operations for all exponents of `64·E` bits, e.g. the words
+    /// returned by `to_limbs` of a secret scalar
+    pub fn pow_ct<const E: usize>(&self, exp: &[u64; E]) -> Self {
+        // invariant: r1 = r0·self
+        let mut r0 = Self::one();
+        let mut r1 = *self;
+        for i in (0..64 * E).rev() {
+            let bit = Choice::from(((exp[i / 64] >> (i % 64)) & 1) as u8);
+            Self::conditional_swap(&mut r0, &mut r1, bit);
+            r1 = r0 * r1;
+            r0 = r0.square();
+            Self::conditional_swap(&mut r0, &mut r1, bit);
+        }
+        r0
+    }
+
+    /// like `invert`, but without branching on the value
+    pub fn invert_ct(&self) -> CtOption<Self> {
+        CtOption::new(self.pow_limbs(&Self::ORDER_MINUS_TWO), !self.ct_eq(&Self::zero()))
+    }
+
+    fn square_n(&self, n: usize) -> Self {
+        (0..n).fold(*self, |value, _| value.square())
+    }
+}
+
+impl<const LIMBS: usize, P: FieldParams<LIMBS>> From<u64> for Fp<LIMBS, P> {
+    fn from(value: u64) -> Self {
+        Self::from_small(value)
+    }
+}
+
+impl<const LIMBS: usize, P: FieldParams<LIMBS>> fmt::Debug for Fp<LIMBS, P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Fp").field(&*Wiped(self.value())).finish()
+    }
+}
+
+impl<const LIMBS: usize, P: FieldParams<LIMBS>> Element<AtomicElement> for Fp<LIMBS, P> {
+    type FieldType = FpField<LIMBS, P>;
+
+    /// the field is not stored in the element, so this returns the shared
+    /// instance of `FpField::instance`
+    fn field(&self) -> Arc<Self::FieldType> {
+        FpField::instance()
+    }
+
+    fn invert(&self) -> Option<Self> {
+        if self.is_zero() {
+            None
+        } else {
+            Some(self.pow_limbs(&Self::ORDER_MINUS_TWO))
+        }
+    }
+
+    fn square(&self) -> Self {
+        Self::from_limbs(limbs::mont_sqr(&self.limbs, &Self::MODULUS, Self::Q_INV_NEG))
+    }
+
+    fn double(&self) -> Self {
+        Self::from_limbs(limbs::add_mod(&self.limbs, &self.limbs, &Self::MODULUS))
+    }
+
+    fn halve(&self) -> Self {
+        Self::from_limbs(limbs::halve_mod(&self.limbs, &Self::MODULUS))
+    }
+
+    /// Euler's criterion
+    fn is_sqrt(&self) -> bool {
+        self.is_zero() || self.pow_limbs(&Self::HALF_ORDER).is_one()
+    }
+
+    // Tonelli-Shanks algorithm
+    fn sqrt(&self) -> Option<(Self, Self)> {
+        if self.is_zero() {
+            return Some((*self, *self));
+        }
+        if !self.is_sqrt() {
+            return None;
+        }
+
+        let mut c = Self::nqr().pow_limbs(&Self::ODD);
+        let mut t = self.pow_limbs(&Self::ODD);
+        // self^((ODD + 1) / 2)
+        let mut r = self.pow_limbs(&limbs::shr(&Self::ODD, 1)) * self;
+        let mut m = Self::TWO_ADICITY;
+        while !t.is_one() {
+            let mut i = 1;
+            while !t.square_n(i).is_one() {
+                i += 1;
+            }
+            let b = c.square_n(m - i - 1);
+            r = r * b;
+            c = b.square();
+            t = t * c;
+            m = i;
+        }
+        Some((r, -r))
+    }
+
+    /// multiplies with `n` reduced modulo the order of the field
+    fn mul_zn(&self, n: &Zr) -> Self {
+        self * Self::from_words(&Wiped(n.to_limbs()))
+    }
+
+    fn pow(&self, exp: &Integer) -> Self {
+        if exp < &Integer::zero() {
+            return self.invert().expect("unable to invert").pow(&-exp);
+        }
+        self.pow_limbs(&Wiped(exp.to_u64_digits()))
+    }
+
+    fn pow_zn(&self, exp: &Zr) -> Self {
+        self.pow_limbs(&Wiped(exp.to_limbs()))
+    }
+
+    /// all elements belong to the same field, so the `try_*` methods only
+    /// fail for a divisor that is not invertible
+    fn try_add(&self, other: &Self) -> Result<Self, PbcError> { Ok(self + other) }
+
+    fn try_sub(&self, other: &Self) -> Result<Self, PbcError> { Ok(self - other) }
+
+    fn try_mul(&self, other: &Self) -> Result<Self, PbcError> { Ok(self * other) }
+
+    fn try_div(&self, other: &Self) -> Result<Self, PbcError> {
+        other.invert().map(|inverse| self * inverse).ok_or(PbcError::NotInvertible)
+    }
+
+    fn try_set(self, other: &Self) -> Result<Self, PbcError> { Ok(*other) }
+}
+
+impl<const LIMBS: usize, P: FieldParams<LIMBS>> Set<&Fp<LIMBS, P>> for Fp<LIMBS, P> {
+    fn set(self, other: &Self) -> Self { *other }
+}
+
+impl<const LIMBS: usize, P: FieldParams<LIMBS>> CanBeZero for Fp<LIMBS, P> {
+    fn is_zero(&self) -> bool { self.ct_eq(&Self::zero()).into() }
+}
+
+impl<const LIMBS: usize, P: FieldParams<LIMBS>> CanBeOne for Fp<LIMBS, P> {
+    fn is_one(&self) -> bool { self.ct_eq(&Self::one()).into() }
+}
+
+impl<const LIMBS: usize, P: FieldParams<LIMBS>> ConstantTimeEq for Fp<LIMBS, P> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.limbs[..].ct_eq(&other.limbs[..])
+    }
+}
+
+/// compares in constant time
+impl<const LIMBS: usize, P: FieldParams<LIMBS>> PartialEq for Fp<LIMBS, P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<const LIMBS: usize, P: FieldParams<LIMBS>> Eq for Fp<LIMBS, P> {}
+
+impl<const LIMBS: usize, P: FieldParams<LIMBS>> ConditionallySelectable for Fp<LIMBS, P> {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        let mask = (choice.unwrap_u8() as u64).wrapping_neg();
+        Self::from_limbs(limbs::select(&a.limbs, &b.limbs, mask))
+    }
+}
+
+impl<const LIMBS: usize, P: FieldParams<LIMBS>> Neg for Fp<LIMBS, P> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::from_limbs(limbs::sub_mod(&[0; LIMBS], &self.limbs, &Self::MODULUS))
+    }
+}
+
+/// implements the operator for references, and forwards the combinations
+/// with values to it
+macro_rules! add_operators {
+    ($($tr:ident $method:ident |$lhs:ident, $rhs:ident| $body:expr;)+) => {
+        $(
+            impl<const LIMBS: usize, P: FieldParams<LIMBS>> $tr<&Fp<LIMBS, P>> for &Fp<LIMBS, P> {
+                type Output = Fp<LIMBS, P>;
+                fn $method(self, rhs: &Fp<LIMBS, P>) -> Fp<LIMBS, P> {
+                    let ($lhs, $rhs) = (self, rhs);
+                    $body
+                }
+            }
+            impl<const LIMBS: usize, P: FieldParams<LIMBS>> $tr<Fp<LIMBS, P>> for &Fp<LIMBS, P> {
+                type Output = Fp<LIMBS, P>;
+                fn $method(self, rhs: Fp<LIMBS, P>) -> Fp<LIMBS, P> { $tr::$method(self, &rhs) }
+            }
+            impl<const LIMBS: usize, P: FieldParams<LIMBS>> $tr<&Fp<LIMBS, P>> for Fp<LIMBS, P> {
+                type Output = Fp<LIMBS, P>;
+                fn $method(self, rhs: &Fp<LIMBS, P>) -> Fp<LIMBS, P> { $tr::$method(&self, rhs) }
+            }
+            impl<const LIMBS: usize, P: FieldParams<LIMBS>> $tr<Fp<LIMBS, P>> for Fp<LIMBS, P> {
+                type Output = Fp<LIMBS, P>;
+                fn $method(self, rhs: Fp<LIMBS, P>) -> Fp<LIMBS, P> { $tr::$method(&self, &rhs) }
+            }
+        )+
+    };
+}
+add_operators! {
+    Add add |lhs, rhs| Fp::from_limbs(limbs::add_mod(&lhs.limbs, &rhs.limbs, &Fp::<LIMBS, P>::MODULUS));
+    Sub sub |lhs, rhs| Fp::from_limbs(limbs::sub_mod(&lhs.limbs, &rhs.limbs, &Fp::<LIMBS, P>::MODULUS));
+    Mul mul |lhs, rhs| Fp::from_limbs(limbs::mont_mul(&lhs.limbs, &rhs.limbs, &Fp::<LIMBS, P>::MODULUS, Fp::<LIMBS, P>::Q_INV_NEG));
+    Div div |lhs, rhs| lhs.try_div(rhs).unwrap_or_else(|e| panic!("{}", e));
+}
+
+/// fixed-width big-endian representation
+impl<const LIMBS: usize, P: FieldParams<LIMBS>> ToBytes for Fp<LIMBS, P> {
+    fn length_in_bytes(&self) -> usize {
+        Self::BITS.div_ceil(8)
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let bytes: Vec<u8> = self.to_limbs().iter().rev().flat_map(|limb| limb.to_be_bytes()).collect();
+        bytes[bytes.len() - self.length_in_bytes()..].to_vec()
+    }
+}
+
+impl<const LIMBS: usize, P: FieldParams<LIMBS>> FromBytes<FpField<LIMBS, P>> for Fp<LIMBS, P> {
+    fn from_bytes(field: Arc<FpField<LIMBS, P>>, bytes: &[u8]) -> Result<Self, BytesError> {
+        check_length(field.as_ref(), bytes)?;
+        let mut canonical = [0; LIMBS];
+        for (i, byte) in bytes.iter().rev().enumerate() {
+            canonical[i / 8] |= (*byte as u64) << (8 * (i % 8));
+        }
+        if !Self::is_canonical(&canonical) {
+            return Err(BytesError::OutOfRange);
+        }
+        Ok(Self::from_canonical(canonical))
+    }
+}
+
+/// declares a prime field with a modulus that is known at compile time: the
+/// parameters `$params`, the element type `$name` and the field `$field`.
+/// The modulus is given as decimal or hexadecimal (with prefix `0x`)
+/// string, which must fit into `$limbs` 64 bit words.
+///
+/// The modulus must be an odd prime, and its primality is the caller's
+/// responsibility: the compile-time constants only reject even moduli, and
+/// moduli for which the search of a quadratic nonresidue fails, e.g.
+///
+/// ```compile_fail
+/// pbc4rust::fp_field!(pub F, FField, FParams, 1, "49");
+///
+/// let nqr = F::nqr();
+/// ```
+///
+/// ```
+/// use pbc4rust::prelude::*;
+/// use std::sync::Arc;
+///
+/// pbc4rust::fp_field!(pub F, FField, FParams, 1, "0xadd1");
+///
+/// let a = F::from(3);
+/// assert_eq!(a * F::from(5), F::from(15));
+/// assert_eq!(a.invert().unwrap() * a, FField::one_element(Arc::new(FField::new())));
+/// assert_eq!(FField::new().order(), &Integer::from(44497));
+/// ```
+#[macro_export]
+macro_rules! fp_field {
+    ($vis:vis $name:ident, $field:ident, $params:ident, $limbs:expr, $modulus:expr) => {
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        $vis struct $params;
+
+        impl $crate::field::FieldParams<{ $limbs }> for $params {
+            const MODULUS: &'static str = $modulus;
+        }
+
+        $vis type $name = $crate::field::Fp<{ $limbs }, $params>;
+        $vis type $field = $crate::field::FpField<{ $limbs }, $params>;
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pbc::*;
+    use crate::pbc::testlib::algebra::*;
+    use crate::test_one;
+    use crate::test_zero;
+    use crate::test_associativity;
+    use crate::test_commutativity;
+    use crate::test_double_and_halve;
+    use crate::test_distributivity;
+    use crate::test_square_and_sqrt;
+    use crate::test_nqr;
+    use crate::test_neg;
+    use crate::test_pow;
+    use crate::test_invert;
+    use crate::test_bytes;
+
+    // the order of the tests of `Zr`, and 2^127 - 1
+    crate::fp_field!(F1, F1Field, F1Params, 1, "44497");
+    crate::fp_field!(F2, F2Field, F2Params, 2, "0x7fff_ffff_ffff_ffff_ffff_ffff_ffff_ffff");
+
+    fn field1() -> Arc<F1Field> { Arc::new(F1Field::new()) }
+    fn field2() -> Arc<F2Field> { Arc::new(F2Field::new()) }
+
+    test_one!(F1, F1Field, field1());
+    test_zero!(F1, F1Field, field1());
+    test_double_and_halve!(F1, F1Field, field1());
+    test_square_and_sqrt!(F1, F1Field, field1());
+    test_commutativity!(F1, mul, F1Field, field1());
+    test_associativity!(F1, mul, F1Field, field1());
+    test_distributivity!(F1, add, mul, F1Field, field1());
+    test_nqr!(F1, F1Field, field1());
+    test_neg!(F1, F1Field, field1());
+    test_pow!(F1, F1Field, field1());
+    test_invert!(F1, F1Field, field1());
+    test_bytes!(F1, F1Field, field1());
+
+    test_one!(F2, F2Field, field2());
+    test_zero!(F2, F2Field, field2());
+    test_double_and_halve!(F2, F2Field, field2());
+    test_square_and_sqrt!(F2, F2Field, field2());
+    test_associativity!(F2, mul, F2Field, field2());
+    test_distributivity!(F2, add, mul, F2Field, field2());
+    test_neg!(F2, F2Field, field2());
+    test_invert!(F2, F2Field, field2());
+    test_bytes!(F2, F2Field, field2());
+
+    /// the results must agree with the GMP based `Zr`
+    #[test]
+    fn test_compare_with_zr() {
+        let zr_field = Arc::new(ZrField::new(Integer::from(ORDER)));
+        for _ in 0..20 {
+            let a = F1Field::random_element(field1());
+            let b = F1Field::random_element(field1());
+            let (za, zb) = (Zr::new(a.value(), Arc::clone(&zr_field)), Zr::new(b.value(), Arc::clone(&zr_field)));
+            assert_eq!((a + b).value(), (&za + &zb).value());
+            assert_eq!((a - b).value(), (&za - &zb).value());
+            assert_eq!((a * b).value(), (&za * &zb).value());
+            assert_eq!((-a).value(), (-za.clone()).value());
+            assert_eq!(a.is_sqrt(), za.is_sqrt());
+        }
+    }
+
+    #[test]
+    fn test_value() {
+        let mut value = Integer::zero();
+        value.setbit(126);
+        assert_eq!(F2::new(&value).value(), value);
+        assert_eq!(F2::new(&-Integer::one()).value(), F2::modulus() - Integer::one());
+        assert_eq!(F2::modulus().bit_length(), 127);
+        assert_eq!(F2::from(2).to_bytes().len(), 16);
+        assert_eq!(F1::nqr(), F1::from(5));
+        assert_eq!(F2::nqr(), F2::from(3));
+        assert_eq!(F1::from(44497 + 7), F1::from(7));
+        assert_eq!(F1::from(u64::MAX).value(), Integer::from(u64::MAX).modulus(&F1::modulus()));
+        assert!(F1::one().is_one());
+        assert!(F1::zero().is_zero());
+    }
+
+    #[test]
+    fn test_pow_ct() {
+        let a = F2Field::random_element(field2());
+        let exponent = F2Field::random_element(field2());
+        assert_eq!(a.pow_ct(&exponent.to_limbs()), a.pow(&exponent.value()));
+        assert_eq!(a.pow_ct(&[0, 0, 0]), F2::one());
+        assert_eq!(a.pow_ct(&[1]), a);
+        assert_eq!(a.pow_ct(&[u64::MAX]), a.pow(&Integer::from(u64::MAX)));
+        assert_eq!(F1::from(3).pow_ct(&[5]), F1::from(243));
+    }
+
+    #[test]
+    fn test_constant_time_operations() {
+        let a = F2Field::random_element(field2());
+        let b = a + F2::one();
+        assert!(bool::from(a.ct_eq(&a)));
+        assert!(!bool::from(a.ct_eq(&b)));
+        assert_eq!(F2::conditional_select(&a, &b, Choice::from(0)), a);
+        assert_eq!(F2::conditional_select(&a, &b, Choice::from(1)), b);
+
+        assert_eq!(a.invert_ct().unwrap() * a, F2::one());
+        assert!(bool::from(F2::zero().invert_ct().is_none()));
+    }
+
+    #[test]
+    fn test_zn_operations() {
+        let zr_field = Arc::new(ZrField::new(Integer::from(ORDER)));
+        let a = F2Field::random_element(field2());
+        let n = ZrField::random_element(zr_field);
+        assert_eq!(a.mul_zn(&n), a * F2::new(&n.value()));
+        // scalars of two words, which are larger than the modulus of `F1`
+        let m = ZrField::random_element(Arc::new(ZrField::new(F2::modulus())));
+        let b = F1Field::random_element(field1());
+        assert_eq!(b.mul_zn(&m), b * F1::new(&m.value()));
+        assert_eq!(a.pow_zn(&n), a.pow(&n.value()));
+        assert_eq!(a.pow(&Integer::from(-3)), a.invert().unwrap().pow(&Integer::from(3)));
+        assert_eq!(a.try_div(&F2::zero()), Err(PbcError::NotInvertible));
+        assert_eq!(a.try_set(&F2::one()), Ok(F2::one()));
+    }
+
+    #[test]
+    fn test_field() {
+        let a = F1::from(3);
+        assert!(Arc::ptr_eq(&a.field(), &F1Field::instance()));
+        assert!(!Arc::ptr_eq(&a.field(), &field1()));
+        assert_eq!(a.field().order(), &F1::modulus());
+        assert_eq!(F2::one().field().order(), &F2::modulus());
+    }
+
+    #[test]
+    fn test_div_by_zero() {
+        let error = std::panic::catch_unwind(|| F1::one() / F1::zero()).unwrap_err();
+        let message = error.downcast_ref::<String>().unwrap();
+        assert_eq!(message, &PbcError::NotInvertible.to_string());
+    }
+}
@@ -0,0 +1,81 @@
+use crate::pbc::Integer;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+use rand::*;
+use super::{ByteLength, Fp, FieldParams};
+use super::traits::*;
+
+/// the prime field of `Fp`. All parameters are part of the type, so that
+/// every instance is equal; it only stores the order as `Integer` to
+/// implement `FiniteField`. `instance` shares one field per type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FpField<const LIMBS: usize, P: FieldParams<LIMBS>> {
+    order: Integer,
+    params: PhantomData<P>,
+}
+
+impl<const LIMBS: usize, P: FieldParams<LIMBS>> FpField<LIMBS, P> {
+    pub fn new() -> Self {
+        FpField { order: Fp::<LIMBS, P>::modulus(), params: PhantomData }
+    }
+
+    /// the field which is shared by all callers, it is created on the first
+    /// call for the parameters `P`
+    pub fn instance() -> Arc<Self> {
+        static FIELDS: OnceLock<Mutex<HashMap<TypeId, Arc<dyn Any + Send + Sync>>>> = OnceLock::new();
+        let mut fields = FIELDS.get_or_init(Default::default).lock().unwrap_or_else(PoisonError::into_inner);
+        let field = fields.entry(TypeId::of::<Self>()).or_insert_with(|| Arc::new(Self::new()));
+        Arc::clone(field).downcast().expect("the fields are stored by their type")
+    }
+}
+
+impl<const LIMBS: usize, P: FieldParams<LIMBS>> Default for FpField<LIMBS, P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const LIMBS: usize, P: FieldParams<LIMBS>> Field<Fp<LIMBS, P>, AtomicElement> for FpField<LIMBS, P> {
+    /// draws words with the bit length of the order until their value is
+    /// below the order
+    fn random_element_with<R: RngCore + CryptoRng>(self: Arc<Self>, rng: &mut R) -> Fp<LIMBS, P> {
+        let bits = Fp::<LIMBS, P>::bits();
+        loop {
+            let mut value = [0; LIMBS];
+            for (i, limb) in value.iter_mut().enumerate() {
+                *limb = match bits.saturating_sub(64 * i) {
+                    0 => 0,
+                    b if b >= 64 => rng.next_u64(),
+                    b => rng.next_u64() >> (64 - b),
+                };
+            }
+            if Fp::<LIMBS, P>::is_canonical(&value) {
+                return Fp::from_canonical(value);
+            }
+        }
+    }
+}
+
+impl<const LIMBS: usize, P: FieldParams<LIMBS>> HasZero<Fp<LIMBS, P>> for FpField<LIMBS, P> {
+    fn zero_element(self: Arc<Self>) -> Fp<LIMBS, P> { Fp::zero() }
+}
+
+impl<const LIMBS: usize, P: FieldParams<LIMBS>> HasOne<Fp<LIMBS, P>> for FpField<LIMBS, P> {
+    fn one_element(self: Arc<Self>) -> Fp<LIMBS, P> { Fp::one() }
+}
+
+impl<const LIMBS: usize, P: FieldParams<LIMBS>> FiniteField<Fp<LIMBS, P>, AtomicElement> for FpField<LIMBS, P> {
+    fn order(&self) -> &Integer { &self.order }
+}
+
+impl<const LIMBS: usize, P: FieldParams<LIMBS>> HasNqr<Fp<LIMBS, P>, AtomicElement> for FpField<LIMBS, P> {
+    fn nqr(_field: Arc<Self>) -> Fp<LIMBS, P> { Fp::nqr() }
+}
+
+impl<const LIMBS: usize, P: FieldParams<LIMBS>> ByteLength for FpField<LIMBS, P> {
+    fn length_in_bytes(&self) -> usize {
+        Fp::<LIMBS, P>::bits().div_ceil(8)
+    }
+}
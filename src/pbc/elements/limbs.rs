@@ -0,0 +1,540 @@
+//! arithmetic on fixed-width little-endian arrays of 64 bit limbs, which
+//! does not allocate. The `const` functions derive the constants of `Fp`
+//! at compile time.
+//!
+//! The modular operations do not branch on their operands, so that they
+//! run in constant time. `less_than`, `bit_length` and `trailing_zeros` are
+//! only meant for public values.
+
+/// a + b + carry, returns the result and the new carry
+#[inline(always)]
+pub(crate) const fn adc(a: u64, b: u64, carry: u64) -> (u64, u64) {
+    let t = a as u128 + b as u128 + carry as u128;
+    (t as u64, (t >> 64) as u64)
+}
+
+/// a - b - borrow, returns the result and the new borrow (0 or 1)
+#[inline(always)]
+pub(crate) const fn sbb(a: u64, b: u64, borrow: u64) -> (u64, u64) {
+    let t = (a as u128).wrapping_sub(b as u128 + borrow as u128);
+    (t as u64, (t >> 127) as u64)
+}
+
+/// a + b·c + carry, returns the result and the new carry
+#[inline(always)]
+pub(crate) const fn mac(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
+    let t = a as u128 + b as u128 * c as u128 + carry as u128;
+    (t as u64, (t >> 64) as u64)
+}
+
+/// the limbs of a value below 2^64
+pub(crate) const fn small<const N: usize>(value: u64) -> [u64; N] {
+    let mut result = [0; N];
+    result[0] = value;
+    result
+}
+
+/// a < b
+pub(crate) const fn less_than<const N: usize>(a: &[u64; N], b: &[u64; N]) -> bool {
+    let mut i = N;
+    while i > 0 {
+        i -= 1;
+        if a[i] != b[i] {
+            return a[i] < b[i];
+        }
+    }
+    false
+}
+
+pub(crate) const fn bit_length<const N: usize>(a: &[u64; N]) -> usize {
+    let mut i = N;
+    while i > 0 {
+        i -= 1;
+        if a[i] != 0 {
+            return i * 64 + 64 - a[i].leading_zeros() as usize;
+        }
+    }
+    0
+}
+
+pub(crate) const fn bit(a: &[u64], index: usize) -> bool {
+    (a[index / 64] >> (index % 64)) & 1 == 1
+}
+
+pub(crate) const fn trailing_zeros<const N: usize>(a: &[u64; N]) -> usize {
+    let mut i = 0;
+    while i < N {
+        if a[i] != 0 {
+            return i * 64 + a[i].trailing_zeros() as usize;
+        }
+        i += 1;
+    }
+    N * 64
+}
+
+/// a >> bits
+pub(crate) const fn shr<const N: usize>(a: &[u64; N], bits: usize) -> [u64; N] {
+    let mut result = [0; N];
+    let (words, bits) = (bits / 64, bits % 64);
+    let mut i = 0;
+    while i + words < N {
+        result[i] = a[i + words] >> bits;
+        if bits > 0 && i + words + 1 < N {
+            result[i] |= a[i + words + 1] << (64 - bits);
+        }
+        i += 1;
+    }
+    result
+}
+
+/// a - b, the borrow is dropped
+pub(crate) const fn sub<const N: usize>(a: &[u64; N], b: &[u64; N]) -> [u64; N] {
+    let mut result = [0; N];
+    let mut borrow = 0;
+    let mut i = 0;
+    while i < N {
+        let (d, b) = sbb(a[i], b[i], borrow);
+        result[i] = d;
+        borrow = b;
+        i += 1;
+    }
+    result
+}
+
+/// returns `a` if `mask` is zero and `b` if all bits of `mask` are set
+pub(crate) const fn select<const N: usize>(a: &[u64; N], b: &[u64; N], mask: u64) -> [u64; N] {
+    let mut result = [0; N];
+    let mut i = 0;
+    while i < N {
+        result[i] = a[i] ^ (mask & (a[i] ^ b[i]));
+        i += 1;
+    }
+    result
+}
+
+/// subtracts q from the value `high`·2^(64·N) + `a`, if it is at least q.
+/// `high` must be 0 or 1.
+const fn reduce_once<const N: usize>(a: &[u64; N], high: u64, q: &[u64; N]) -> [u64; N] {
+    let mut difference = [0; N];
+    let mut borrow = 0;
+    let mut i = 0;
+    while i < N {
+        let (d, b) = sbb(a[i], q[i], borrow);
+        difference[i] = d;
+        borrow = b;
+        i += 1;
+    }
+    // the difference is taken if there was no borrow, or if it was absorbed
+    // by `high`
+    select(a, &difference, (high | (borrow ^ 1)).wrapping_neg())
+}
+
+/// a + b mod q for a, b < q
+pub(crate) const fn add_mod<const N: usize>(a: &[u64; N], b: &[u64; N], q: &[u64; N]) -> [u64; N] {
+    let mut sum = [0; N];
+    let mut carry = 0;
+    let mut i = 0;
+    while i < N {
+        let (s, c) = adc(a[i], b[i], carry);
+        sum[i] = s;
+        carry = c;
+        i += 1;
+    }
+    reduce_once(&sum, carry, q)
+}
+
+/// a - b mod q for a, b < q
+pub(crate) const fn sub_mod<const N: usize>(a: &[u64; N], b: &[u64; N], q: &[u64; N]) -> [u64; N] {
+    let mut result = [0; N];
+    let mut borrow = 0;
+    let mut i = 0;
+    while i < N {
+        let (d, b) = sbb(a[i], b[i], borrow);
+        result[i] = d;
+        borrow = b;
+        i += 1;
+    }
+
+    // adds q if there was a borrow
+    let mask = borrow.wrapping_neg();
+    let mut carry = 0;
+    let mut i = 0;
+    while i < N {
+        let (s, c) = adc(result[i], q[i] & mask, carry);
+        result[i] = s;
+        carry = c;
+        i += 1;
+    }
+    result
+}
+
+/// a / 2 mod q for a < q and an odd q
+pub(crate) const fn halve_mod<const N: usize>(a: &[u64; N], q: &[u64; N]) -> [u64; N] {
+    // adds q if a is odd, so that the sum is even
+    let mask = (a[0] & 1).wrapping_neg();
+    let mut value = [0; N];
+    let mut carry = 0;
+    let mut i = 0;
+    while i < N {
+        let (s, c) = adc(a[i], q[i] & mask, carry);
+        value[i] = s;
+        carry = c;
+        i += 1;
+    }
+    let mut i = 0;
+    while i < N {
+        let next = if i + 1 < N { value[i + 1] } else { carry };
+        value[i] = (value[i] >> 1) | (next << 63);
+        i += 1;
+    }
+    value
+}
+
+/// Montgomery multiplication a·b·R⁻¹ mod q with R = 2^(64·N), using the
+/// coarsely integrated operand scanning (CIOS) method
+pub(crate) const fn mont_mul<const N: usize>(a: &[u64; N], b: &[u64; N], q: &[u64; N], q_inv_neg: u64) -> [u64; N] {
+    let mut t = [0; N];
+    let mut t_high = 0;
+    let mut i = 0;
+    while i < N {
+        // t += a·b[i]
+        let mut carry = 0;
+        let mut j = 0;
+        while j < N {
+            let (s, c) = mac(t[j], a[j], b[i], carry);
+            t[j] = s;
+            carry = c;
+            j += 1;
+        }
+        let (s, t_overflow) = adc(t_high, carry, 0);
+        t_high = s;
+
+        // t = (t + m·q) / 2^64, where m is chosen so that the division is exact
+        let m = t[0].wrapping_mul(q_inv_neg);
+        let (_, mut carry) = mac(t[0], m, q[0], 0);
+        let mut j = 1;
+        while j < N {
+            let (s, c) = mac(t[j], m, q[j], carry);
+            t[j - 1] = s;
+            carry = c;
+            j += 1;
+        }
+        let (s, c) = adc(t_high, carry, 0);
+        t[N - 1] = s;
+        t_high = t_overflow + c;
+        i += 1;
+    }
+    reduce_once(&t, t_high, q)
+}
+
+/// a²·R⁻¹ mod q like `mont_mul`, but the products a[i]·a[j] with i ≠ j are
+/// only calculated once, and the reduction follows the separated operand
+/// scanning (SOS) method
+pub(crate) fn mont_sqr<const N: usize>(a: &[u64; N], q: &[u64; N], q_inv_neg: u64) -> [u64; N] {
+    // the 2·N words of a², where t[1] holds the upper half
+    let mut words = [[0; N]; 2];
+    let t = words.as_flattened_mut();
+
+    // the products below the diagonal
+    for i in 0..N {
+        let mut carry = 0;
+        for j in (i + 1)..N {
+            let (s, c) = mac(t[i + j], a[i], a[j], carry);
+            t[i + j] = s;
+            carry = c;
+        }
+        t[i + N] = carry;
+    }
+
+    // doubles them and adds the squares a[i]²
+    let mut top = 0;
+    for word in t.iter_mut() {
+        let doubled = (*word << 1) | top;
+        top = *word >> 63;
+        *word = doubled;
+    }
+    let mut carry = 0;
+    for i in 0..N {
+        let (s, c) = mac(t[2 * i], a[i], a[i], carry);
+        t[2 * i] = s;
+        let (s, c) = adc(t[2 * i + 1], c, 0);
+        t[2 * i + 1] = s;
+        carry = c;
+    }
+
+    // adds m·q·2^(64·i) for each word, so that the lower half becomes zero
+    let mut high = 0;
+    for i in 0..N {
+        let m = t[i].wrapping_mul(q_inv_neg);
+        let mut carry = 0;
+        for j in 0..N {
+            let (s, c) = mac(t[i + j], m, q[j], carry);
+            t[i + j] = s;
+            carry = c;
+        }
+        let (s, c) = adc(t[i + N], carry, high);
+        t[i + N] = s;
+        high = c;
+    }
+    reduce_once(&words[1], high, q)
+}
+
+/// -q⁻¹ mod 2^64 for an odd q
+pub(crate) const fn q_inv_neg(q0: u64) -> u64 {
+    // q0^(2^63 - 1) = q0⁻¹, because q0^(2^62) = 1
+    let mut inv: u64 = 1;
+    let mut i = 0;
+    while i < 63 {
+        inv = inv.wrapping_mul(inv);
+        inv = inv.wrapping_mul(q0);
+        i += 1;
+    }
+    inv.wrapping_neg()
+}
+
+/// a^exp in Montgomery representation with sliding windows of up to 5 bits,
+/// where `one` is the representation of one and `exp` consists of
+/// little-endian words. The time depends on the exponent, which has to be
+/// public.
+pub(crate) fn mont_pow<const N: usize>(a: &[u64; N], exp: &[u64], one: &[u64; N], q: &[u64; N], q_inv_neg: u64) -> [u64; N] {
+    const WINDOW: usize = 5;
+    // the odd powers a, a^3, ..., a^31
+    let a2 = mont_sqr(a, q, q_inv_neg);
+    let mut table = [*a; 1 << (WINDOW - 1)];
+    for i in 1..table.len() {
+        table[i] = mont_mul(&table[i - 1], &a2, q, q_inv_neg);
+    }
+
+    let mut result = *one;
+    let mut i = exp.len() * 64;
+    while i > 0 {
+        if !bit(exp, i - 1) {
+            result = mont_sqr(&result, q, q_inv_neg);
+            i -= 1;
+            continue;
+        }
+        // the longest window of bits i-1, ..., low which ends with a one
+        let mut low = i.saturating_sub(WINDOW);
+        while !bit(exp, low) {
+            low += 1;
+        }
+        let mut window = 0;
+        for k in (low..i).rev() {
+            result = mont_sqr(&result, q, q_inv_neg);
+            window = (window << 1) | bit(exp, k) as usize;
+        }
+        result = mont_mul(&result, &table[window >> 1], q, q_inv_neg);
+        i = low;
+    }
+    result
+}
+
+/// a mod d for a nonzero d below 2^64
+pub(crate) const fn rem_small<const N: usize>(a: &[u64; N], d: u64) -> u64 {
+    let mut remainder = 0;
+    let mut i = N;
+    while i > 0 {
+        i -= 1;
+        remainder = ((((remainder as u128) << 64) | a[i] as u128) % d as u128) as u64;
+    }
+    remainder
+}
+
+/// the Jacobi symbol (a/q) of a small a and an odd q > 1, which is the
+/// Legendre symbol for a prime q. By quadratic reciprocity, the large q
+/// only appears in q mod 8 and q mod a.
+pub(crate) const fn jacobi_small<const N: usize>(a: u64, q: &[u64; N]) -> i32 {
+    let mut result = 1;
+    let mut a = a;
+    if a == 0 {
+        return 0;
+    }
+    while a & 1 == 0 {
+        a /= 2;
+        if q[0] % 8 == 3 || q[0] % 8 == 5 {
+            result = -result;
+        }
+    }
+    if a % 4 == 3 && q[0] % 4 == 3 {
+        result = -result;
+    }
+
+    // (q/a) = (q mod a / a) for the odd a
+    let mut n = a;
+    let mut a = rem_small(q, n);
+    while a != 0 {
+        while a & 1 == 0 {
+            a /= 2;
+            if n % 8 == 3 || n % 8 == 5 {
+                result = -result;
+            }
+        }
+        let swap = a;
+        a = n;
+        n = swap;
+        if a % 4 == 3 && n % 4 == 3 {
+            result = -result;
+        }
+        a %= n;
+    }
+    if n == 1 { result } else { 0 }
+}
+
+/// 2^exp mod q, by doubling one exp times
+pub(crate) const fn pow2_mod<const N: usize>(exp: usize, q: &[u64; N]) -> [u64; N] {
+    let mut value = [0; N];
+    value[0] = 1;
+    let mut i = 0;
+    while i < exp {
+        value = add_mod(&value, &value, q);
+        i += 1;
+    }
+    value
+}
+
+/// parses a decimal or hexadecimal (with prefix `0x`) number, and fails to
+/// compile if it has invalid digits or does not fit into N limbs.
+/// Underscores are ignored.
+pub(crate) const fn parse<const N: usize>(number: &str) -> [u64; N] {
+    let bytes = number.as_bytes();
+    let (radix, mut pos) = if bytes.len() > 2 && bytes[0] == b'0' && (bytes[1] == b'x' || bytes[1] == b'X') {
+        (16, 2)
+    } else {
+        (10, 0)
+    };
+    let mut value = [0; N];
+    let mut digits = 0;
+    while pos < bytes.len() {
+        let digit = match bytes[pos] {
+            b'_' => {
+                pos += 1;
+                continue;
+            }
+            b @ b'0'..=b'9' => (b - b'0') as u64,
+            b @ b'a'..=b'f' if radix == 16 => (b - b'a' + 10) as u64,
+            b @ b'A'..=b'F' if radix == 16 => (b - b'A' + 10) as u64,
+            _ => panic!("invalid digit in modulus"),
+        };
+
+        // value = value·radix + digit
+        let mut carry = digit;
+        let mut i = 0;
+        while i < N {
+            let (s, c) = mac(0, value[i], radix, carry);
+            value[i] = s;
+            carry = c;
+            i += 1;
+        }
+        if carry != 0 {
+            panic!("the modulus does not fit into the limbs");
+        }
+        digits += 1;
+        pos += 1;
+    }
+    if digits == 0 {
+        panic!("the modulus has no digits");
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jacobi_small() {
+        // the squares modulo 11 are 1, 3, 4, 5 and 9
+        let residues: Vec<i32> = (0..11).map(|a| jacobi_small(a, &[11])).collect();
+        assert_eq!(residues, [0, 1, -1, 1, 1, 1, -1, -1, -1, 1, -1]);
+        assert_eq!(jacobi_small(21, &[7]), 0);
+        assert_eq!(rem_small(&[5, 1], 7), (((1_u128 << 64) + 5) % 7) as u64);
+        // 2^127 - 1 ≡ 7 mod 8, so 2 is a square, and ≡ 1 mod 3
+        let q = [u64::MAX, u64::MAX >> 1];
+        assert_eq!(jacobi_small(2, &q), 1);
+        assert_eq!(jacobi_small(3, &q), -1);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse::<1>("44497"), [44497]);
+        assert_eq!(parse::<1>("0xadd1"), [44497]);
+        assert_eq!(parse::<2>("0x1_0000_0000_0000_0001"), [1, 1]);
+        assert_eq!(parse::<2>("340282366920938463463374607431768211455"), [u64::MAX, u64::MAX]);
+    }
+
+    #[test]
+    fn test_mont_mul() {
+        // q = 2^127 - 1, R = 2^128
+        let q = [u64::MAX, u64::MAX >> 1];
+        let inv = q_inv_neg(q[0]);
+        assert_eq!(q[0].wrapping_mul(inv), u64::MAX);
+
+        let r = pow2_mod(128, &q);
+        let r2 = pow2_mod(256, &q);
+        assert_eq!(r, [2, 0]);
+        let a = [0x1234_5678_9abc_def0, 0x0fed_cba9_8765_4321];
+        let encoded = mont_mul(&a, &r2, &q, inv);
+        assert_eq!(mont_mul(&encoded, &[1, 0], &q, inv), a);
+        assert_eq!(mont_mul(&encoded, &r, &q, inv), encoded);
+
+        for a in [encoded, [0, 0], [u64::MAX - 1, u64::MAX >> 1], [1, 0]] {
+            assert_eq!(mont_sqr(&a, &q, inv), mont_mul(&a, &a, &q, inv));
+        }
+        // q = 2^255 - 19
+        let q = [u64::MAX - 18, u64::MAX, u64::MAX, u64::MAX >> 1];
+        let inv = q_inv_neg(q[0]);
+        let mut a = [0x0123_4567_89ab_cdef, 0xfedc_ba98_7654_3210, u64::MAX, 0x7fff_ffff_ffff_ffec];
+        for _ in 0..100 {
+            assert_eq!(mont_sqr(&a, &q, inv), mont_mul(&a, &a, &q, inv));
+            a = mont_mul(&a, &[3, 5, 7, 11], &q, inv);
+        }
+    }
+
+    #[test]
+    fn test_mont_pow() {
+        // q = 2^127 - 1, R = 2^128
+        let q = [u64::MAX, u64::MAX >> 1];
+        let inv = q_inv_neg(q[0]);
+        let one = pow2_mod(128, &q);
+        let a = mont_mul(&[3, 0], &pow2_mod(256, &q), &q, inv);
+
+        let mut expected = one;
+        for exp in 0..40_u64 {
+            assert_eq!(mont_pow(&a, &[exp], &one, &q, inv), expected);
+            expected = mont_mul(&expected, &a, &q, inv);
+        }
+        // Fermat's little theorem
+        assert_eq!(mont_pow(&a, &[u64::MAX - 1, u64::MAX >> 1], &one, &q, inv), one);
+        assert_eq!(mont_pow(&a, &[], &one, &q, inv), one);
+    }
+
+    #[test]
+    fn test_shr() {
+        let a = [0x0123_4567_89ab_cdef, 0xfedc_ba98_7654_3210];
+        assert_eq!(shr(&a, 0), a);
+        assert_eq!(shr(&a, 4), [0x0012_3456_789a_bcde, 0x0fed_cba9_8765_4321]);
+        assert_eq!(shr(&a, 68), [0x0fed_cba9_8765_4321, 0]);
+        assert_eq!(trailing_zeros(&[0, 8]), 67);
+        assert_eq!(bit_length(&a), 128);
+    }
+
+    #[test]
+    fn test_select() {
+        assert_eq!(select(&[1, 2], &[3, 4], 0), [1, 2]);
+        assert_eq!(select(&[1, 2], &[3, 4], u64::MAX), [3, 4]);
+    }
+
+    #[test]
+    fn test_add_sub_halve() {
+        let q = [44497];
+        assert_eq!(add_mod(&[44496], &[2], &q), [1]);
+        assert_eq!(add_mod(&[44496], &[0], &q), [44496]);
+        assert_eq!(add_mod(&[u64::MAX - 1, 0], &[u64::MAX - 1, 0], &[u64::MAX, 0]), [u64::MAX - 2, 0]);
+        // the sum overflows the limbs
+        assert_eq!(add_mod(&[u64::MAX - 1], &[u64::MAX - 1], &[u64::MAX]), [u64::MAX - 2]);
+        assert_eq!(sub_mod(&[1], &[2], &q), [44496]);
+        assert_eq!(halve_mod(&[1], &q), [22249]);
+        let half = halve_mod(&[12345], &q);
+        assert_eq!(add_mod(&half, &half, &q), [12345]);
+    }
+}
@@ -4,11 +4,33 @@ mod quadratic;
 mod z_field;
 mod zr_field;
 mod quadratic_field;
+mod limbs;
+mod fp;
+mod fp_field;
 pub mod traits;
+mod bytes;
+mod montgomery;
+mod pow_preprocessed;
+mod multi_exp;
+mod wiped;
+mod secret;
+#[cfg(feature = "serde")]
+pub(crate) mod serialization;
+#[cfg(feature = "zeroize")]
+mod zeroization;
 
 pub use z::*;
 pub use zr::*;
 pub use quadratic::*;
 pub use z_field::*;
 pub use zr_field::*;
-pub use quadratic_field::*;
\ No newline at end of file
+pub use quadratic_field::*;
+pub use fp::*;
+pub use fp_field::*;
+pub use bytes::*;
+pub use pow_preprocessed::*;
+pub use multi_exp::*;
+pub use secret::*;
+pub(crate) use wiped::*;
+#[cfg(feature = "serde")]
+pub use serialization::ElementSeed;
\ No newline at end of file
@@ -0,0 +1,278 @@
+use crate::pbc::{BigInteger, Integer};
+use super::limbs::{self, adc, mac, q_inv_neg, sbb};
+use super::Wiped;
+use std::convert::TryInto;
+
+/// returns the result of the fixed-width function of `limbs` for the
+/// number of words of the modulus, if there is an instance for it. The
+/// loops of these functions are unrolled, which is considerably faster.
+macro_rules! fixed_width {
+    ($montgomery:expr, $words:ident => $body:expr) => {
+        fixed_width!(@ $montgomery, $words => $body; 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16)
+    };
+    (@ $montgomery:expr, $words:ident => $body:expr; $($n:literal)+) => {
+        match $montgomery.modulus.len() {
+            $($n => {
+                const $words: usize = $n;
+                return $body.to_vec();
+            })+
+            _ => {}
+        }
+    };
+}
+
+/// constants for the Montgomery representation a·R mod q of elements of a
+/// prime field, where R = 2^(64·n) for the number n of 64 bit words of q.
+///
+/// Values are stored as n little-endian words below q. The arithmetic
+/// works on the words directly and does not branch on the values.
+#[derive(Debug, Clone)]
+pub(crate) struct Montgomery {
+    order: Integer,
+    modulus: Vec<u64>,
+    /// -q⁻¹ mod 2^64
+    q_inv_neg: u64,
+    /// R mod q, which is the representation of one
+    r: Vec<u64>,
+    /// R² mod q, which is used to convert into the representation
+    r2: Vec<u64>,
+    /// R³ mod q, which corrects the inverse of a representation
+    r3: Vec<u64>,
+}
+
+impl Montgomery {
+    /// requires `modulus` to be odd
+    pub fn new(modulus: &Integer) -> Montgomery {
+        let n = modulus.bit_length().div_ceil(64);
+        let mut r = Integer::zero();
+        r.setbit(n * 64);
+        let r = r.modulus(modulus);
+        let r2 = (&r * &r).modulus(modulus);
+        let r3 = (&r2 * &r).modulus(modulus);
+
+        let limbs = to_limbs(modulus, n);
+        assert!(limbs[0] & 1 == 1, "the modulus must be odd");
+        Montgomery {
+            order: modulus.clone(),
+            q_inv_neg: q_inv_neg(limbs[0]),
+            modulus: limbs,
+            r: to_limbs(&r, n),
+            r2: to_limbs(&r2, n),
+            r3: to_limbs(&r3, n),
+        }
+    }
+
+    pub fn one(&self) -> &[u64] { &self.r }
+
+    pub fn zero(&self) -> Vec<u64> { vec![0; self.modulus.len()] }
+
+    /// converts a value in [0, q) into the Montgomery representation
+    pub fn encode(&self, value: &Integer) -> Vec<u64> {
+        self.mul(&Wiped(to_limbs(value, self.modulus.len())), &self.r2)
+    }
+
+    pub fn decode(&self, value: &[u64]) -> Integer {
+        from_limbs(&Wiped(self.to_canonical(value)))
+    }
+
+    /// converts back to the words of the value in [0, q)
+    pub fn to_canonical(&self, value: &[u64]) -> Vec<u64> {
+        let mut one = self.zero();
+        one[0] = 1;
+        self.mul(value, &one)
+    }
+
+    /// a·b·R⁻¹ mod q, using the coarsely integrated operand scanning (CIOS)
+    /// method of `limbs::mont_mul`
+    pub fn mul(&self, a: &[u64], b: &[u64]) -> Vec<u64> {
+        fixed_width!(self, WORDS => limbs::mont_mul::<WORDS>(words(a), words(b), words(&self.modulus), self.q_inv_neg));
+
+        let n = self.modulus.len();
+        // the common length lets the compiler drop the bounds checks
+        let (q, a, b) = (&self.modulus[..n], &a[..n], &b[..n]);
+        let mut t = vec![0; n];
+        let mut t_high = 0;
+        for &b_i in b {
+            // t += a·b[i]
+            let mut carry = 0;
+            for j in 0..n {
+                let (s, c) = mac(t[j], a[j], b_i, carry);
+                t[j] = s;
+                carry = c;
+            }
+            let (s, t_overflow) = adc(t_high, carry, 0);
+            t_high = s;
+
+            // t = (t + m·q) / 2^64, where m is chosen so that the division is exact
+            let m = t[0].wrapping_mul(self.q_inv_neg);
+            let (_, mut carry) = mac(t[0], m, q[0], 0);
+            for j in 1..n {
+                let (s, c) = mac(t[j], m, q[j], carry);
+                t[j - 1] = s;
+                carry = c;
+            }
+            let (s, c) = adc(t_high, carry, 0);
+            t[n - 1] = s;
+            t_high = t_overflow + c;
+        }
+        self.reduce_once(t, t_high)
+    }
+
+    pub fn square(&self, a: &[u64]) -> Vec<u64> {
+        fixed_width!(self, WORDS => limbs::mont_sqr::<WORDS>(words(a), words(&self.modulus), self.q_inv_neg));
+        self.mul(a, a)
+    }
+
+    /// a^exp for a non-negative exponent, which has to be public
+    pub fn pow(&self, a: &[u64], exp: &Integer) -> Vec<u64> {
+        let exp = Wiped(exp.to_u64_digits());
+        fixed_width!(self, WORDS => limbs::mont_pow::<WORDS>(words(a), &exp, words(&self.r), words(&self.modulus), self.q_inv_neg));
+
+        // square-and-multiply
+        let mut result = self.r.clone();
+        for i in (0..exp.len() * 64).rev() {
+            result = self.square(&result);
+            if limbs::bit(&exp, i) {
+                result = self.mul(&result, a);
+            }
+        }
+        result
+    }
+
+    pub fn add(&self, a: &[u64], b: &[u64]) -> Vec<u64> {
+        let mut carry = 0;
+        let sum = a.iter().zip(b).map(|(a, b)| {
+            let (s, c) = adc(*a, *b, carry);
+            carry = c;
+            s
+        }).collect();
+        self.reduce_once(sum, carry)
+    }
+
+    pub fn sub(&self, a: &[u64], b: &[u64]) -> Vec<u64> {
+        let mut borrow = 0;
+        let difference = a.iter().zip(b).map(|(a, b)| {
+            let (d, c) = sbb(*a, *b, borrow);
+            borrow = c;
+            d
+        }).collect();
+        // adds q if there was a borrow
+        self.add_masked(difference, borrow.wrapping_neg())
+    }
+
+    pub fn neg(&self, a: &[u64]) -> Vec<u64> {
+        self.sub(&self.zero(), a)
+    }
+
+    /// a / 2 mod q, which does not depend on the representation
+    pub fn halve(&self, a: &[u64]) -> Vec<u64> {
+        // adds q if a is odd, so that the sum is even
+        let mut value = a.to_vec();
+        let mut carry = 0;
+        let mask = (a[0] & 1).wrapping_neg();
+        for (v, q) in value.iter_mut().zip(&self.modulus) {
+            let (s, c) = adc(*v, q & mask, carry);
+            *v = s;
+            carry = c;
+        }
+        for i in 0..value.len() {
+            let next = if i + 1 < value.len() { value[i + 1] } else { carry };
+            value[i] = (value[i] >> 1) | (next << 63);
+        }
+        value
+    }
+
+    /// the representation a·R has the inverse a⁻¹·R⁻¹, which is corrected
+    /// by a multiplication with R³
+    pub fn invert(&self, a: &[u64]) -> Option<Vec<u64>> {
+        let inverse = Wiped(BigInteger::invert(&*Wiped(from_limbs(a)), &self.order)?);
+        Some(self.mul(&Wiped(to_limbs(&inverse, self.modulus.len())), &self.r3))
+    }
+
+    /// adds q & mask to a, dropping the carry
+    fn add_masked(&self, mut a: Vec<u64>, mask: u64) -> Vec<u64> {
+        let mut carry = 0;
+        for (a, q) in a.iter_mut().zip(&self.modulus) {
+            let (s, c) = adc(*a, q & mask, carry);
+            *a = s;
+            carry = c;
+        }
+        a
+    }
+
+    /// subtracts q from the value `high`·R + `a`, if it is at least q.
+    /// `high` must be 0 or 1.
+    fn reduce_once(&self, a: Vec<u64>, high: u64) -> Vec<u64> {
+        let mut borrow = 0;
+        for (a, q) in a.iter().zip(&self.modulus) {
+            borrow = sbb(*a, *q, borrow).1;
+        }
+        // q is subtracted if there was no borrow, or if it is absorbed by
+        // `high`, so the words of the difference can be computed in place
+        let mask = (high | (borrow ^ 1)).wrapping_neg();
+        let mut borrow = 0;
+        let mut a = a;
+        for (a, q) in a.iter_mut().zip(&self.modulus) {
+            let (d, b) = sbb(*a, q & mask, borrow);
+            *a = d;
+            borrow = b;
+        }
+        a
+    }
+}
+
+/// the words of a value of the fixed-width functions
+fn words<const N: usize>(value: &[u64]) -> &[u64; N] {
+    value.try_into().expect("the value has the width of the modulus")
+}
+
+/// the little-endian words of a non-negative value below 2^(64·n)
+fn to_limbs(value: &Integer, n: usize) -> Vec<u64> {
+    let mut limbs = value.to_u64_digits();
+    limbs.resize(n, 0);
+    limbs
+}
+
+fn from_limbs(limbs: &[u64]) -> Integer {
+    Integer::from_u64_digits(limbs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conversion() {
+        let q = Integer::from_str_radix("27015232578650219", 10).unwrap();
+        let mut large_q = Integer::zero();
+        large_q.setbit(127);
+        let large_q = large_q - Integer::one();
+        let mut p521 = Integer::zero();
+        p521.setbit(521);
+        let p521 = p521 - Integer::one();
+
+        for q in [q, large_q, p521, Integer::from(44497)] {
+            let montgomery = Montgomery::new(&q);
+            let a = Integer::from(123456789).modulus(&q);
+            let b = &q - Integer::from(2);
+            let (ma, mb) = (montgomery.encode(&a), montgomery.encode(&b));
+            assert_eq!(montgomery.decode(&ma), a);
+            assert_eq!(montgomery.decode(montgomery.one()), Integer::one());
+            assert_eq!(montgomery.decode(&montgomery.mul(&ma, &mb)), (&a * &b).modulus(&q));
+            assert_eq!(montgomery.decode(&montgomery.mul(&mb, &mb)), (&b * &b).modulus(&q));
+            assert_eq!(montgomery.square(&mb), montgomery.mul(&mb, &mb));
+            assert_eq!(montgomery.decode(&montgomery.add(&ma, &mb)), (&a + &b).modulus(&q));
+            assert_eq!(montgomery.decode(&montgomery.add(&mb, &mb)), (&b + &b).modulus(&q));
+            assert_eq!(montgomery.decode(&montgomery.sub(&ma, &mb)), (&a - &b).modulus(&q));
+            assert_eq!(montgomery.decode(&montgomery.sub(&mb, &ma)), (&b - &a).modulus(&q));
+            assert_eq!(montgomery.decode(&montgomery.neg(&ma)), (-&a).modulus(&q));
+            assert_eq!(montgomery.neg(&montgomery.zero()), montgomery.zero());
+            assert_eq!(montgomery.decode(&montgomery.add(&montgomery.halve(&ma), &montgomery.halve(&ma))), a);
+            assert_eq!(montgomery.decode(&montgomery.pow(&ma, &b)), BigInteger::powm(&a, &b, &q));
+            assert_eq!(montgomery.pow(&ma, &Integer::zero()), montgomery.one());
+            let inverse = montgomery.decode(&montgomery.invert(&mb).unwrap());
+            assert_eq!((&inverse * &b).modulus(&q), Integer::one());
+            assert_eq!(montgomery.invert(&montgomery.zero()), None);
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use crate::pbc::Integer;
+use super::traits::*;
+use super::Zr;
+
+/// below this number of bases, Straus' algorithm is faster than Pippenger's
+const PIPPENGER_THRESHOLD: usize = 32;
+
+/// window size of Straus' algorithm
+const STRAUS_WINDOW_SIZE: usize = 4;
+
+/// calculates the product of bases[i]^scalars[i], which is the sum of the
+/// scalar multiples for curve points.
+///
+/// Straus' interleaving algorithm is used for few bases, Pippenger's
+/// bucket algorithm for many bases. Both require `bases` to be non-empty
+/// and of the same length as `scalars`.
+///
+/// ```
+/// use pbc4rust::prelude::*;
+/// use std::sync::Arc;
+///
+/// let field = Arc::new(ZrField::new(Integer::from(44497)));
+/// let bases = vec![Zr::new(Integer::from(2), Arc::clone(&field)), Zr::new(Integer::from(3), Arc::clone(&field))];
+/// let scalars = vec![Zr::new(Integer::from(5), Arc::clone(&field)), Zr::new(Integer::from(4), Arc::clone(&field))];
+/// assert_eq!(multi_exp(&bases, &scalars), Zr::new(Integer::from(32 * 81), field));
+/// ```
+pub fn multi_exp<E: Element<T>, T: ElementLevel>(bases: &[E], scalars: &[Zr]) -> E {
+    assert!(! bases.is_empty(), "at least one base is required");
+    assert_eq!(bases.len(), scalars.len());
+
+    let scalars: Vec<Integer> = scalars.iter().map(Zr::value).collect();
+    if bases.len() < PIPPENGER_THRESHOLD {
+        straus(bases, &scalars)
+    } else {
+        pippenger(bases, &scalars)
+    }
+}
+
+fn max_bit_length(scalars: &[Integer]) -> usize {
+    scalars.iter().map(Integer::bit_length).max().unwrap_or(0)
+}
+
+/// returns the `size` bits of `scalar` starting at bit `offset`
+fn digit(scalar: &Integer, offset: usize, size: usize) -> usize {
+    (offset..offset + size).rev()
+        .fold(0, |digit, bit| (digit << 1) | (scalar.tstbit(bit) as usize))
+}
+
+/// processes all scalars window by window, using tables of the powers
+/// base^0, ..., base^(2^w - 1) of every base
+fn straus<E: Element<T>, T: ElementLevel>(bases: &[E], scalars: &[Integer]) -> E {
+    let one = bases[0].field().one_element();
+    let tables: Vec<Vec<E>> = bases.iter().map(|base| {
+        let mut table = vec![one.clone()];
+        for j in 1..(1 << STRAUS_WINDOW_SIZE) {
+            let next = table[j - 1].clone() * base;
+            table.push(next);
+        }
+        table
+    }).collect();
+
+    let windows = max_bit_length(scalars).div_ceil(STRAUS_WINDOW_SIZE);
+    let mut result = one;
+    for window in (0..windows).rev() {
+        for _ in 0..STRAUS_WINDOW_SIZE {
+            result = result.square();
+        }
+        for (table, scalar) in tables.iter().zip(scalars) {
+            let digit = digit(scalar, window * STRAUS_WINDOW_SIZE, STRAUS_WINDOW_SIZE);
+            if digit != 0 {
+                result = result * &table[digit];
+            }
+        }
+    }
+    result
+}
+
+/// sorts the bases into buckets by the digits of their scalars in every
+/// window, so that each base is multiplied only once per window
+fn pippenger<E: Element<T>, T: ElementLevel>(bases: &[E], scalars: &[Integer]) -> E {
+    let one = bases[0].field().one_element();
+    let window_size = match bases.len().ilog2() as usize {
+        b if b > 3 => b - 2,
+        _ => 1,
+    };
+
+    let windows = max_bit_length(scalars).div_ceil(window_size);
+    let mut result = one.clone();
+    for window in (0..windows).rev() {
+        for _ in 0..window_size {
+            result = result.square();
+        }
+
+        let mut buckets = vec![one.clone(); (1 << window_size) - 1];
+        for (base, scalar) in bases.iter().zip(scalars) {
+            let digit = digit(scalar, window * window_size, window_size);
+            if digit != 0 {
+                buckets[digit - 1] = buckets[digit - 1].clone() * base;
+            }
+        }
+
+        // the product of bucket[j]^(j + 1), using running products
+        let mut running = one.clone();
+        let mut sum = one.clone();
+        for bucket in buckets.iter().rev() {
+            running = running * bucket;
+            sum = sum * &running;
+        }
+        result = result * sum;
+    }
+    result
+}
@@ -0,0 +1,89 @@
+use crate::pbc::Integer;
+use std::marker::PhantomData;
+use super::traits::*;
+use super::{Wiped, Zr};
+
+/// number of exponent bits which are handled by a single table lookup, as
+/// in PBC's `element_pp_init`
+const WINDOW_SIZE: usize = 5;
+
+/// precomputed powers of a fixed base, like PBC's `element_pp_t`.
+///
+/// For every window of `WINDOW_SIZE` exponent bits, the table contains
+/// base^(j·2^(WINDOW_SIZE·i)) for all digits j, so that `pow` requires only
+/// one multiplication per window and no squarings.
+///
+/// ```
+/// use pbc4rust::prelude::*;
+/// use std::sync::Arc;
+///
+/// let field = Arc::new(ZrField::new(Integer::from(44497)));
+/// let g = Zr::new(Integer::from(3), Arc::clone(&field));
+/// let pp = PowPreprocessed::new(&g, 16);
+/// assert_eq!(pp.pow(&Integer::from(12345)), g.pow(&Integer::from(12345)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct PowPreprocessed<E: Element<T>, T: ElementLevel> {
+    base: E,
+    bits: usize,
+    table: Vec<Vec<E>>,
+    phantom: PhantomData<T>,
+}
+
+impl<E: Element<T>, T: ElementLevel> PowPreprocessed<E, T> {
+    /// precomputes the table for exponents of up to `bits` bits, which
+    /// usually is the bit length of the group order
+    pub fn new(base: &E, bits: usize) -> Self {
+        let one = base.field().one_element();
+        let windows = bits.div_ceil(WINDOW_SIZE);
+        let mut table = Vec::with_capacity(windows);
+        let mut window_base = base.clone();
+        for _ in 0..windows {
+            let mut row = Vec::with_capacity(1 << WINDOW_SIZE);
+            row.push(one.clone());
+            for j in 1..(1 << WINDOW_SIZE) {
+                let next = row[j - 1].clone() * &window_base;
+                row.push(next);
+            }
+            // the base of the next window is window_base^(2^WINDOW_SIZE)
+            window_base = row[(1 << WINDOW_SIZE) - 1].clone() * &window_base;
+            table.push(row);
+        }
+
+        Self {
+            base: base.clone(),
+            bits,
+            table,
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn base(&self) -> &E { &self.base }
+
+    /// calculates base^exp. Exponents with more than `bits` bits are
+    /// supported, but do not benefit from the precomputation.
+    pub fn pow(&self, exp: &Integer) -> E {
+        if exp < &Integer::zero() {
+            return self.pow(&-exp).invert().expect("unable to invert");
+        }
+        if exp.bit_length() > self.bits {
+            return self.base.pow(exp);
+        }
+
+        let mut result = self.table[0][0].clone();
+        for (i, row) in self.table.iter().enumerate() {
+            let mut digit = 0;
+            for bit in (i * WINDOW_SIZE..(i + 1) * WINDOW_SIZE).rev() {
+                digit = (digit << 1) | (exp.tstbit(bit) as usize);
+            }
+            if digit != 0 {
+                result = result * &row[digit];
+            }
+        }
+        result
+    }
+
+    pub fn pow_zn(&self, exp: &Zr) -> E {
+        self.pow(&Wiped(exp.value()))
+    }
+}
@@ -0,0 +1,34 @@
+use crate::pbc::*;
+use std::sync::Arc;
+
+/// x followed by y
+impl<E, F> ToBytes for Quadratic<E, F>
+where
+    E: Element<AtomicElement> + ToBytes,
+    F: HasZero<E> + HasNqr<E, AtomicElement>,
+{
+    fn length_in_bytes(&self) -> usize {
+        self.x.length_in_bytes() + self.y.length_in_bytes()
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.x.to_bytes();
+        bytes.extend(self.y.to_bytes());
+        bytes
+    }
+}
+
+impl<E, F> FromBytes<QuadraticField<E, F>> for Quadratic<E, F>
+where
+    E: Element<AtomicElement> + FromBytes<F>,
+    F: HasZero<E> + HasNqr<E, AtomicElement> + ByteLength,
+{
+    fn from_bytes(field: Arc<QuadraticField<E, F>>, bytes: &[u8]) -> Result<Self, BytesError> {
+        check_length(field.as_ref(), bytes)?;
+        let target_field = field.target_field();
+        let (x, y) = bytes.split_at(target_field.length_in_bytes());
+        let x = E::from_bytes(Arc::clone(&target_field), x)?;
+        let y = E::from_bytes(target_field, y)?;
+        Ok(Quadratic::new(x, y, field))
+    }
+}
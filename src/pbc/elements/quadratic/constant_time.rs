@@ -0,0 +1,23 @@
+use crate::pbc::*;
+use subtle::{Choice, ConstantTimeEq};
+
+impl<E, F> ConditionallyAssignable for Quadratic<E, F>
+where
+    E: Element<AtomicElement> + ConditionallyAssignable,
+    F: HasZero<E> + HasNqr<E, AtomicElement>,
+{
+    fn conditional_assign(&mut self, other: &Self, choice: Choice) {
+        self.x.conditional_assign(&other.x, choice);
+        self.y.conditional_assign(&other.y, choice);
+    }
+}
+
+impl<E, F> ConstantTimeEq for Quadratic<E, F>
+where
+    E: Element<AtomicElement> + ConstantTimeEq,
+    F: HasZero<E> + HasNqr<E, AtomicElement>,
+{
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.x.ct_eq(&other.x) & self.y.ct_eq(&other.y)
+    }
+}
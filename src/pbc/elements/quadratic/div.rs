@@ -0,0 +1,46 @@
+use crate::pbc::*;
+use std::ops::Div;
+
+impl<'b, E, F> Div<&'b Quadratic<E, F>> for &Quadratic<E, F>
+where
+    E: Element<AtomicElement>,
+    F: HasZero<E> + HasNqr<E, AtomicElement>,
+{
+    type Output = Quadratic<E, F>;
+    fn div(self, rhs: &'b Quadratic<E, F>) -> Self::Output {
+        self.try_div(rhs).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl<E, F> Div<Quadratic<E, F>> for &Quadratic<E, F>
+where
+    E: Element<AtomicElement>,
+    F: HasZero<E> + HasNqr<E, AtomicElement>,
+{
+    type Output = Quadratic<E, F>;
+    fn div(self, rhs: Quadratic<E, F>) -> Self::Output {
+        self / &rhs
+    }
+}
+
+impl<'b, E, F> Div<&'b Quadratic<E, F>> for Quadratic<E, F>
+where
+    E: Element<AtomicElement>,
+    F: HasZero<E> + HasNqr<E, AtomicElement>,
+{
+    type Output = Quadratic<E, F>;
+    fn div(self, rhs: &'b Quadratic<E, F>) -> Self::Output {
+        &self / rhs
+    }
+}
+
+impl<E, F> Div<Quadratic<E, F>> for Quadratic<E, F>
+where
+    E: Element<AtomicElement>,
+    F: HasZero<E> + HasNqr<E, AtomicElement>,
+{
+    type Output = Quadratic<E, F>;
+    fn div(self, rhs: Quadratic<E, F>) -> Self::Output {
+        &self / &rhs
+    }
+}
@@ -1,7 +1,7 @@
 use super::QuadraticField;
-use crate::pbc::traits::{Element};
+use crate::pbc::traits::{Element, check_same_field};
 use crate::pbc::*;
-use std::rc::Rc;
+use std::sync::Arc;
 
 impl<E, F> SuperElement<E, AtomicElement> for Quadratic<E, F>
 where
@@ -18,12 +18,12 @@ where
 {
     type FieldType = QuadraticField<E, F>;
 
-    fn field(&self) -> Rc<Self::FieldType> {
-        Rc::clone(&self.field)
+    fn field(&self) -> Arc<Self::FieldType> {
+        Arc::clone(&self.field)
     }
 
     fn double(&self) -> Self {
-        Self::new(self.x.double(), self.y.double(), Rc::clone(&self.field))
+        Self::new(self.x.double(), self.y.double(), Arc::clone(&self.field))
     }
     fn halve(&self) -> Self {
         Self {
@@ -33,14 +33,85 @@ where
         }
     }
 
+    /// (x + y·√nqr)⁻¹ = (x - y·√nqr) / (x² - nqr·y²)
+    fn invert(&self) -> Option<Self> {
+        let norm_inverse = self.norm().invert()?;
+        Some(Self::new(
+            self.x.clone() * &norm_inverse,
+            self.y.clone().neg() * &norm_inverse,
+            Arc::clone(&self.field),
+        ))
+    }
+
+    /// (x + y·√nqr)² = (x + y)·(x + nqr·y) - (1 + nqr)·xy + 2xy·√nqr
     fn square(&self) -> Self {
-        self.clone()
+        let nqr = F::nqr(self.field.target_field());
+        let xy = self.x.clone() * &self.y;
+        let x = (self.x.clone() + &self.y) * (self.y.clone() * &nqr + &self.x) - &xy - &(xy.clone() * &nqr);
+        Self::new(x, xy.double(), Arc::clone(&self.field))
     }
+
+    /// an element is a square iff its norm is a square in the base field
     fn is_sqrt(&self) -> bool {
-        false
+        self.norm().is_sqrt()
     }
 
+    /// with s² = x² - nqr·y² and t² = (x ± s) / 2, the square root of
+    /// x + y·√nqr is t + y / 2t·√nqr
     fn sqrt(&self) -> Option<(Self, Self)> {
-        None
+        let target_field = self.field.target_field();
+        let root = if self.y.is_zero() {
+            let zero = Arc::clone(&target_field).zero_element();
+            match self.x.sqrt() {
+                Some((t, _)) => Self::new(t, zero, Arc::clone(&self.field)),
+                None => {
+                    // x is a nonresidue, so x / nqr is a square
+                    let (u, _) = (self.x.clone() / F::nqr(target_field)).sqrt()?;
+                    Self::new(zero, u, Arc::clone(&self.field))
+                }
+            }
+        } else {
+            let (s, _) = self.norm().sqrt()?;
+            let (t, _) = match (self.x.clone() + &s).halve().sqrt() {
+                Some(roots) => roots,
+                None => (self.x.clone() - &s).halve().sqrt()?,
+            };
+            let u = self.y.clone() / t.double();
+            Self::new(t, u, Arc::clone(&self.field))
+        };
+        Some((root.clone(), -root))
+    }
+
+    fn try_add(&self, other: &Self) -> Result<Self, PbcError> {
+        check_same_field(self, other)?;
+        Ok(Self::new(self.x.clone() + &other.x, self.y.clone() + &other.y, Arc::clone(&self.field)))
+    }
+
+    fn try_sub(&self, other: &Self) -> Result<Self, PbcError> {
+        check_same_field(self, other)?;
+        Ok(Self::new(self.x.clone() - &other.x, self.y.clone() - &other.y, Arc::clone(&self.field)))
+    }
+
+    /// (x1 + y1·√nqr)·(x2 + y2·√nqr) = x1·x2 + nqr·y1·y2 + (x1·y2 + y1·x2)·√nqr
+    fn try_mul(&self, other: &Self) -> Result<Self, PbcError> {
+        check_same_field(self, other)?;
+        let nqr = F::nqr(self.field.target_field());
+        let e0 = self.x.clone() * &other.x;
+        let e1 = self.y.clone() * &other.y;
+        let e1 = e1 * &nqr;
+        let x = e0 + e1;
+
+        let e1 = self.x.clone() * &other.y;
+        let e2 = self.y.clone() * &other.x;
+        let y = e1 + e2;
+
+        Ok(Self::new(x, y, Arc::clone(&self.field)))
+    }
+
+    fn try_set(mut self, other: &Self) -> Result<Self, PbcError> {
+        check_same_field(&self, other)?;
+        self.x = other.x.clone();
+        self.y = other.y.clone();
+        Ok(self)
     }
 }
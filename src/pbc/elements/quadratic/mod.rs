@@ -1,34 +1,31 @@
-use super::traits::{Element, FiniteField};
+use super::traits::Element;
 use super::QuadraticField;
 use crate::pbc::*;
-use std::ops::{Add, Mul, Sub};
-use std::rc::Rc;
+use std::ops::{Add, Sub};
 
-pub mod quadratic;
-pub mod one;
-pub mod zero;
-pub mod neg;
-pub mod set;
-pub mod element;
-pub mod mul;
+#[allow(clippy::module_inception)]
+mod quadratic;
+mod one;
+mod zero;
+mod neg;
+mod set;
+mod element;
+mod mul;
+mod div;
+mod bytes;
+mod constant_time;
 
 pub use quadratic::Quadratic;
-pub use one::*;
-pub use zero::*;
-pub use neg::*;
-pub use set::*;
-pub use element::*;
-pub use mul::*;
 
 #[allow(unused_macros)]
 macro_rules! add_operator {
-    ($op:tt, $trait:tt, $method: tt ) => {
+    ($trait:tt, $method:tt, $try_method:tt) => {
         impl<E, F> $trait<Self> for Quadratic<E, F>
         where E: Element<AtomicElement>,
         F: HasZero<E> + HasNqr<E, AtomicElement>, {
             type Output=Quadratic<E, F>;
             fn $method(self, rhs: Self) -> Self::Output {
-                Quadratic::new(self.x $op &rhs.x, self.y $op &rhs.y, self.field.clone())
+                self.$try_method(&rhs).unwrap_or_else(|e| panic!("{}", e))
             }
         }
 
@@ -37,7 +34,7 @@ macro_rules! add_operator {
         F: HasZero<E> + HasNqr<E, AtomicElement>, {
             type Output=Quadratic<E, F>;
             fn $method(self, rhs: &Self) -> Self::Output {
-                Quadratic::new(self.x $op &rhs.x, self.y $op &rhs.y, self.field.clone())
+                self.$try_method(rhs).unwrap_or_else(|e| panic!("{}", e))
             }
         }
 
@@ -46,43 +43,166 @@ macro_rules! add_operator {
         F: HasZero<E> + HasNqr<E, AtomicElement>, {
             type Output=Quadratic<E, F>;
             fn $method(self, rhs: &'b Quadratic<E, F>) -> Self::Output {
-                Quadratic::new(self.x.clone() $op &rhs.x, self.y.clone() $op &rhs.y, self.field.clone())
+                self.$try_method(rhs).unwrap_or_else(|e| panic!("{}", e))
             }
         }
     };
 }
 
-add_operator!(+, Add, add);
-add_operator!(-, Sub, sub);
+add_operator!(Add, add, try_add);
+add_operator!(Sub, sub, try_sub);
 
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::ops::{Mul, Neg};
+    use std::sync::Arc;
     use crate::pbc::testlib::algebra::*;
     use crate::test_associativity;
     use crate::test_commutativity;
     use crate::test_distributivity;
     use crate::test_double_and_halve;
     use crate::test_one;
-    //use crate::test_square_and_sqrt;
+    use crate::test_square_and_sqrt;
     use crate::pbc::Zr;
     use crate::pbc::ZrField;
     use crate::test_zero;
-    use gmp::mpz::Mpz;
+    use crate::test_neg;
+    use crate::test_pow;
+    use crate::test_invert;
+    use crate::test_bytes;
+    use crate::test_pow_preprocessed;
+    use crate::test_multi_exp;
+    use crate::pbc::Integer;
 
-    fn field() -> Rc<QuadraticField<Zr, ZrField>> {
-        let zr_field = Rc::new(ZrField::new(Mpz::from(ORDER)));
+    fn field() -> Arc<QuadraticField<Zr, ZrField>> {
+        let zr_field = Arc::new(ZrField::new(Integer::from(ORDER)));
         //let q_order = ZrField::nqr(zr_field.clone());
-        Rc::new(QuadraticField::new(zr_field))
+        Arc::new(QuadraticField::new(zr_field))
     }
     test_one!(Quadratic<Zr, ZrField>, QuadraticField<Zr, ZrField>, field());
     test_zero!(Quadratic<Zr, ZrField>, QuadraticField<Zr, ZrField>, field());
-    //test_double_and_halve!(Quadratic<Zr, ZrField>, QuadraticField<Zr, ZrField>, field());
-    //test_square_and_sqrt!(Quadratic<Zr, ZrField>, QuadraticField<Zr, ZrField>, field());
+    test_neg!(Quadratic<Zr, ZrField>, QuadraticField<Zr, ZrField>, field());
+    test_double_and_halve!(Quadratic<Zr, ZrField>, QuadraticField<Zr, ZrField>, field());
+    test_square_and_sqrt!(Quadratic<Zr, ZrField>, QuadraticField<Zr, ZrField>, field());
     test_commutativity!(Quadratic<Zr, ZrField>, add, QuadraticField<Zr, ZrField>, field());
     test_commutativity!(Quadratic<Zr, ZrField>, mul, QuadraticField<Zr, ZrField>, field());
     test_associativity!(Quadratic<Zr, ZrField>, add, QuadraticField<Zr, ZrField>, field());
     test_associativity!(Quadratic<Zr, ZrField>, mul, QuadraticField<Zr, ZrField>, field());
     test_distributivity!(Quadratic<Zr, ZrField>, add, mul, QuadraticField<Zr, ZrField>, field());
+    test_pow!(Quadratic<Zr, ZrField>, QuadraticField<Zr, ZrField>, field());
+    test_invert!(Quadratic<Zr, ZrField>, QuadraticField<Zr, ZrField>, field());
+    test_bytes!(Quadratic<Zr, ZrField>, QuadraticField<Zr, ZrField>, field());
+    test_pow_preprocessed!(Quadratic<Zr, ZrField>, QuadraticField<Zr, ZrField>, field());
+    test_multi_exp!(Quadratic<Zr, ZrField>, QuadraticField<Zr, ZrField>, field());
+
+    #[test]
+    fn test_pow_with_large_exponents() {
+        let field = field();
+        let a = QuadraticField::random_element(Arc::clone(&field));
+        let e1 = Integer::from_str_radix("d3b1a5f0c4e2976d8a0b13c5f7e9d2a4b6c8e0f1a3b5c7d9e1f2a4b6c8d0e1f3a5", 16).unwrap();
+        let e2 = Integer::from_str_radix("8000000000000000000000000000000000000000000000000000000000001", 16).unwrap();
+
+        // plain square-and-multiply as reference
+        let mut expected = QuadraticField::one_element(field);
+        for i in (0..e1.bit_length()).rev() {
+            expected = &expected * &expected;
+            if e1.tstbit(i) {
+                expected = &expected * &a;
+            }
+        }
+        assert_eq!(a.pow(&e1), expected);
+        assert_eq!(a.pow(&(&e1 + &e2)), a.pow(&e1) * a.pow(&e2));
+        assert_eq!(a.pow(&(&e1 * &e2)), a.pow(&e1).pow(&e2));
+    }
+
+    #[test]
+    fn test_try_operations() {
+        let field = field();
+        let other_field = Arc::new(QuadraticField::new(Arc::new(ZrField::new(Integer::from(44483)))));
+        let a = QuadraticField::random_element(Arc::clone(&field));
+        let b = QuadraticField::random_element(Arc::clone(&field));
+        let c = QuadraticField::random_element(other_field);
+
+        assert_eq!(a.try_add(&b), Ok(&a + &b));
+        assert_eq!(a.try_sub(&b), Ok(&a - &b));
+        assert_eq!(a.try_mul(&b), Ok(&a * &b));
+        assert_eq!(a.clone().try_set(&b), Ok(b.clone()));
+
+        assert_eq!(a.try_add(&c), Err(PbcError::FieldMismatch));
+        assert_eq!(a.try_sub(&c), Err(PbcError::FieldMismatch));
+        assert_eq!(a.try_mul(&c), Err(PbcError::FieldMismatch));
+        assert_eq!(a.try_div(&c), Err(PbcError::FieldMismatch));
+        assert_eq!(a.try_set(&c), Err(PbcError::FieldMismatch));
+
+        let zero = QuadraticField::zero_element(field);
+        assert_eq!(b.try_div(&zero), Err(PbcError::NotInvertible));
+    }
+
+    #[test]
+    #[should_panic(expected = "different fields")]
+    fn test_mul_from_different_fields() {
+        let a = QuadraticField::random_element(field());
+        let b = QuadraticField::random_element(Arc::new(QuadraticField::new(Arc::new(ZrField::new(Integer::from(44483))))));
+        let _ = a * b;
+    }
+
+    #[test]
+    fn test_constant_time_operations() {
+        use subtle::{Choice, ConstantTimeEq};
+        let field = field();
+        let mut a = QuadraticField::random_element(Arc::clone(&field));
+        let mut b = &a + &QuadraticField::one_element(field);
+        let (a0, b0) = (a.clone(), b.clone());
+        assert!(bool::from(a.ct_eq(&a0)));
+        assert!(!bool::from(a.ct_eq(&b)));
+
+        Quadratic::conditional_swap(&mut a, &mut b, Choice::from(0));
+        assert_eq!((&a, &b), (&a0, &b0));
+        Quadratic::conditional_swap(&mut a, &mut b, Choice::from(1));
+        assert_eq!((&a, &b), (&b0, &a0));
+    }
+
+    #[test]
+    fn test_invert_zero() {
+        let zero: Quadratic<Zr, ZrField> = QuadraticField::zero_element(field());
+        assert_eq!(zero.invert(), None);
+    }
+
+    #[test]
+    fn test_square_matches_mul() {
+        let field = field();
+        let a = QuadraticField::random_element(field);
+        assert_eq!(a.square(), &a * &a);
+    }
+
+    #[test]
+    fn test_sqrt_of_base_field_elements() {
+        let field = field();
+        let nqr = ZrField::nqr(field.target_field());
+        let zero = ZrField::zero_element(field.target_field());
+
+        // nqr has no square root in the base field, but √nqr is in the extension
+        let a = Quadratic::new(nqr, zero.clone(), Arc::clone(&field));
+        assert!(a.is_sqrt());
+        let (s1, s2) = a.sqrt().unwrap();
+        assert_eq!(s1.square(), a);
+        assert_eq!(s2.square(), a);
+        assert!(s1.x.is_zero());
+
+        let b = Quadratic::new(zero.clone(), zero, field);
+        assert_eq!(b.sqrt(), Some((b.clone(), b)));
+    }
+
+    #[test]
+    fn test_sqrt_of_nonresidue() {
+        let field = field();
+        let mut a = QuadraticField::random_element(Arc::clone(&field));
+        while a.is_sqrt() {
+            a = QuadraticField::random_element(Arc::clone(&field));
+        }
+        assert!(! a.norm().is_sqrt());
+        assert_eq!(a.sqrt(), None);
+    }
 }
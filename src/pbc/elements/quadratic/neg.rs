@@ -1,6 +1,6 @@
 use crate::pbc::*;
 use std::ops::Neg;
-use std::rc::Rc;
+use std::sync::Arc;
 
 impl<E, F> Neg for Quadratic<E, F>
 where
@@ -10,6 +10,6 @@ where
     type Output = Quadratic<E, F>;
 
     fn neg(self) -> Self {
-        Self::new(self.x.neg(), self.y.neg(), Rc::clone(&self.field))
+        Self::new(self.x.neg(), self.y.neg(), Arc::clone(&self.field))
     }
 }
\ No newline at end of file
@@ -1,11 +1,11 @@
 use crate::pbc::*;
-use std::rc::Rc;
+use std::sync::Arc;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Quadratic<E: Element<AtomicElement>, F: HasZero<E> + HasNqr<E, AtomicElement>> {
     pub x: E,
     pub y: E,
-    pub field: Rc<QuadraticField<E, F>>,
+    pub field: Arc<QuadraticField<E, F>>,
 }
 
 impl<E, F> Quadratic<E, F>
@@ -13,7 +13,19 @@ where
     E: Element<AtomicElement>,
     F: HasZero<E> + HasNqr<E, AtomicElement>,
 {
-    pub fn new(x: E, y: E, field: Rc<QuadraticField<E, F>>) -> Quadratic<E, F> {
+    pub fn new(x: E, y: E, field: Arc<QuadraticField<E, F>>) -> Quadratic<E, F> {
         Self { x, y, field }
     }
+
+    /// calculates x² - nqr·y², which is the product of the element and its
+    /// conjugate
+    pub fn norm(&self) -> E {
+        let nqr = F::nqr(self.field.target_field());
+        self.x.square() - &(self.y.square() * &nqr)
+    }
+
+    /// maps x + y·√nqr to x - y·√nqr
+    pub fn conjugate(&self) -> Quadratic<E, F> {
+        Self::new(self.x.clone(), self.y.clone().neg(), self.field.clone())
+    }
 }
\ No newline at end of file
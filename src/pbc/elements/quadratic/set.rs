@@ -14,15 +14,12 @@ where
 }
 
 
-impl<'b, E, F> Set<&'b Quadratic<E, F>> for Quadratic<E, F>
+impl<E, F> Set<&Quadratic<E, F>> for Quadratic<E, F>
 where
     E: Element<AtomicElement>,
     F: HasZero<E> + HasNqr<E, AtomicElement>,
 {
-    fn set(mut self, element: &Self) -> Self {
-        self.x = element.x.clone();
-        self.y = element.y.clone();
-        self.field = element.field();
-        self
+    fn set(self, element: &Self) -> Self {
+        self.try_set(element).unwrap_or_else(|e| panic!("{}", e))
     }
 }
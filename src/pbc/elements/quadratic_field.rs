@@ -1,7 +1,8 @@
 use super::traits::*;
-use super::Quadratic;
+use super::{ByteLength, Quadratic};
+use rand::{CryptoRng, RngCore};
 use std::marker::PhantomData;
-use std::rc::Rc;
+use std::sync::Arc;
 
 
 #[derive(Debug, Clone, PartialEq)]
@@ -10,8 +11,8 @@ where
     E: Element<AtomicElement>,
     F: HasZero<E> + HasNqr<E, AtomicElement>,
 {
-    //order: Mpz,
-    target_field: Rc<F>,
+    //order: Integer,
+    target_field: Arc<F>,
     phantom: PhantomData<E>,
 }
 
@@ -20,14 +21,13 @@ where
     E: Element<AtomicElement>,
     F: HasZero<E> + HasNqr<E, AtomicElement>,
 {
-    pub fn new(target_field: Rc<F>) -> QuadraticField<E, F> {
+    pub fn new(target_field: Arc<F>) -> QuadraticField<E, F> {
         //let order = target_field.order() * target_field.order();
-        let field = QuadraticField {
+        QuadraticField {
             //order,
             target_field,
             phantom: PhantomData,
-        };
-        field
+        }
     }
 }
 
@@ -36,11 +36,11 @@ where
     E: Element<AtomicElement>,
     F: HasZero<E> + HasNqr<E, AtomicElement>,
 {
-    fn zero_element(self: Rc<Self>) -> Quadratic<E, F> {
+    fn zero_element(self: Arc<Self>) -> Quadratic<E, F> {
         Quadratic::new(
-            Rc::clone(&self.target_field).zero_element(),
-            Rc::clone(&self.target_field).zero_element(),
-            Rc::clone(&self),
+            Arc::clone(&self.target_field).zero_element(),
+            Arc::clone(&self.target_field).zero_element(),
+            Arc::clone(&self),
         )
     }
 }
@@ -50,11 +50,11 @@ where
     E: Element<AtomicElement>,
     F: HasZero<E> + HasNqr<E, AtomicElement>,
 {
-    fn one_element(self: Rc<Self>) -> Quadratic<E, F> {
+    fn one_element(self: Arc<Self>) -> Quadratic<E, F> {
         Quadratic::new(
-            Rc::clone(&self.target_field).one_element(),
-            Rc::clone(&self.target_field).zero_element(),
-            Rc::clone(&self),
+            Arc::clone(&self.target_field).one_element(),
+            Arc::clone(&self.target_field).zero_element(),
+            Arc::clone(&self),
         )
     }
 }
@@ -64,21 +64,31 @@ where
     E: Element<AtomicElement>,
     F: HasZero<E> + HasNqr<E, AtomicElement>,
 {
-    fn random_element(self: Rc<Self>) -> Quadratic<E, F> {
+    fn random_element_with<R: RngCore + CryptoRng>(self: Arc<Self>, rng: &mut R) -> Quadratic<E, F> {
         Quadratic::new(
-            Rc::clone(&self.target_field).random_element(),
-            Rc::clone(&self.target_field).random_element(),
-            Rc::clone(&self),
+            Arc::clone(&self.target_field).random_element_with(rng),
+            Arc::clone(&self.target_field).random_element_with(rng),
+            Arc::clone(&self),
         )
     }
 }
 
+impl<E, F> ByteLength for QuadraticField<E, F>
+where
+    E: Element<AtomicElement>,
+    F: HasZero<E> + HasNqr<E, AtomicElement> + ByteLength,
+{
+    fn length_in_bytes(&self) -> usize {
+        2 * self.target_field.length_in_bytes()
+    }
+}
+
 impl<E, F> FieldOver<Quadratic<E, F>, QuadraticField<E, F>, E, F, AtomicElement> for QuadraticField<E, F>
 where
     E: Element<AtomicElement>,
     F: HasZero<E> + HasNqr<E, AtomicElement>,
 {
-    fn target_field(&self) -> Rc<F> {
-        Rc::clone(&self.target_field)
+    fn target_field(&self) -> Arc<F> {
+        Arc::clone(&self.target_field)
     }
 }
\ No newline at end of file
@@ -0,0 +1,48 @@
+use std::fmt;
+use super::Zr;
+
+/// a secret element of Zr, e.g. a private key, whose value never shows up
+/// in `Debug` output. With the feature `zeroize`, the value is overwritten
+/// when it is dropped.
+#[derive(Clone)]
+pub struct SecretZr(pub(super) Zr);
+
+impl SecretZr {
+    pub fn new(value: Zr) -> SecretZr {
+        SecretZr(value)
+    }
+
+    /// gives access to the secret value, which should not be copied
+    pub fn expose_secret(&self) -> &Zr {
+        &self.0
+    }
+}
+
+impl From<Zr> for SecretZr {
+    fn from(value: Zr) -> Self {
+        SecretZr::new(value)
+    }
+}
+
+impl fmt::Debug for SecretZr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SecretZr([REDACTED])")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pbc::*;
+    use crate::pbc::testlib::algebra::ORDER;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_debug_is_redacted() {
+        let field = Arc::new(ZrField::new(Integer::from(ORDER)));
+        let secret = SecretZr::new(Zr::new(Integer::from(31337), Arc::clone(&field)));
+        assert_eq!(format!("{:?}", secret), "SecretZr([REDACTED])");
+        assert!(!format!("{:#?}", secret).contains("31337"));
+        assert_eq!(secret.expose_secret(), &Zr::new(Integer::from(31337), field));
+    }
+}
@@ -0,0 +1,283 @@
+use crate::pbc::Integer;
+use serde::de::{self, DeserializeSeed, Deserializer, SeqAccess, Visitor};
+use serde::ser::{SerializeTuple, Serializer};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Arc;
+use super::traits::*;
+use super::{FromBytes, Quadratic, QuadraticField, ToBytes, Z, Zr, ZrField};
+
+/// (de)serializes an `Integer` as decimal string, because it might be too large
+/// for any of the integer types of serde
+pub(crate) mod mpz_string {
+    use crate::pbc::Integer;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &Integer, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&value.to_str_radix(10))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Integer, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Integer::from_str_radix(&value, 10).map_err(|_| de::Error::custom(format!("invalid integer: '{}'", value)))
+    }
+}
+
+/// deserializes elements into an existing field, so that all elements
+/// share the same field instance.
+///
+/// ```
+/// use pbc4rust::prelude::*;
+/// use serde::de::DeserializeSeed;
+/// use std::sync::Arc;
+///
+/// let field = Arc::new(ZrField::new(Integer::from(44497)));
+/// let a = Zr::new(Integer::from(12345), Arc::clone(&field));
+/// let json = serde_json::to_string(&a).unwrap();
+///
+/// let mut deserializer = serde_json::Deserializer::from_str(&json);
+/// let b = ElementSeed::new(Arc::clone(&field)).deserialize(&mut deserializer).unwrap();
+/// assert_eq!(a, b);
+/// ```
+pub struct ElementSeed<F> {
+    field: Arc<F>,
+}
+
+impl<F> ElementSeed<F> {
+    pub fn new(field: Arc<F>) -> Self {
+        Self { field }
+    }
+}
+
+/// the layout of `to_bytes`
+impl Serialize for Zr {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&self.to_bytes())
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for ElementSeed<ZrField> {
+    type Value = Zr;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Zr, D::Error> {
+        deserializer.deserialize_bytes(ZrVisitor { field: self.field })
+    }
+}
+
+struct ZrVisitor {
+    field: Arc<ZrField>,
+}
+
+impl<'de> Visitor<'de> for ZrVisitor {
+    type Value = Zr;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "the byte representation of an element of Zr")
+    }
+
+    fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<Zr, E> {
+        Zr::from_bytes(self.field, bytes).map_err(E::custom)
+    }
+
+    /// some formats, e.g. JSON, store bytes as sequence
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Zr, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+        self.visit_bytes(&bytes)
+    }
+}
+
+impl Serialize for Z {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        mpz_string::serialize(self.value(), serializer)
+    }
+}
+
+/// `ZField` has no parameters, so no seed is required
+impl<'de> Deserialize<'de> for Z {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Z, D::Error> {
+        mpz_string::deserialize(deserializer).map(Z::new)
+    }
+}
+
+/// serialized as pair (x, y)
+impl<E, F> Serialize for Quadratic<E, F>
+where
+    E: Element<AtomicElement> + Serialize,
+    F: HasZero<E> + HasNqr<E, AtomicElement>,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut tuple = serializer.serialize_tuple(2)?;
+        tuple.serialize_element(&self.x)?;
+        tuple.serialize_element(&self.y)?;
+        tuple.end()
+    }
+}
+
+impl<'de, E, F> DeserializeSeed<'de> for ElementSeed<QuadraticField<E, F>>
+where
+    E: Element<AtomicElement>,
+    F: HasZero<E> + HasNqr<E, AtomicElement>,
+    ElementSeed<F>: DeserializeSeed<'de, Value = E>,
+{
+    type Value = Quadratic<E, F>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Quadratic<E, F>, D::Error> {
+        deserializer.deserialize_tuple(2, QuadraticVisitor { field: self.field })
+    }
+}
+
+struct QuadraticVisitor<E, F>
+where
+    E: Element<AtomicElement>,
+    F: HasZero<E> + HasNqr<E, AtomicElement>,
+{
+    field: Arc<QuadraticField<E, F>>,
+}
+
+impl<'de, E, F> Visitor<'de> for QuadraticVisitor<E, F>
+where
+    E: Element<AtomicElement>,
+    F: HasZero<E> + HasNqr<E, AtomicElement>,
+    ElementSeed<F>: DeserializeSeed<'de, Value = E>,
+{
+    type Value = Quadratic<E, F>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a pair of elements")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Quadratic<E, F>, A::Error> {
+        let x = seq.next_element_seed(ElementSeed::new(self.field.target_field()))?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let y = seq.next_element_seed(ElementSeed::new(self.field.target_field()))?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        Ok(Quadratic::new(x, y, self.field))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "ZrField")]
+struct ZrFieldParams {
+    #[serde(with = "mpz_string")]
+    order: Integer,
+    #[serde(with = "mpz_string")]
+    nqr: Integer,
+}
+
+impl Serialize for ZrField {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ZrFieldParams {
+            order: self.order().clone(),
+            nqr: ZrField::nqr(Arc::new(self.clone())).value(),
+        }.serialize(serializer)
+    }
+}
+
+/// checks the parameters instead of panicking like `ZrField::with_nqr`
+impl<'de> Deserialize<'de> for ZrField {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<ZrField, D::Error> {
+        let params = ZrFieldParams::deserialize(deserializer)?;
+        ZrField::try_with_nqr(params.order, params.nqr).map_err(de::Error::custom)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "QuadraticField")]
+struct QuadraticFieldParams<F> {
+    target_field: F,
+}
+
+impl<E, F> Serialize for QuadraticField<E, F>
+where
+    E: Element<AtomicElement>,
+    F: HasZero<E> + HasNqr<E, AtomicElement> + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        QuadraticFieldParams { target_field: self.target_field().as_ref() }.serialize(serializer)
+    }
+}
+
+impl<'de, E, F> Deserialize<'de> for QuadraticField<E, F>
+where
+    E: Element<AtomicElement>,
+    F: HasZero<E> + HasNqr<E, AtomicElement> + Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let params = QuadraticFieldParams::<F>::deserialize(deserializer)?;
+        Ok(QuadraticField::new(Arc::new(params.target_field)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pbc::testlib::algebra::ORDER;
+    use crate::pbc::testlib::curve::type_a_options;
+    use crate::pbc::CurveOptions;
+
+    fn field() -> Arc<ZrField> { Arc::new(ZrField::new(Integer::from(ORDER))) }
+
+    fn from_json<'de, S: DeserializeSeed<'de>>(seed: S, json: &'de str) -> Result<S::Value, serde_json::Error> {
+        seed.deserialize(&mut serde_json::Deserializer::from_str(json))
+    }
+
+    #[test]
+    fn test_zr() {
+        let field = field();
+        let a = ZrField::random_element(Arc::clone(&field));
+        let json = serde_json::to_string(&a).unwrap();
+        let b = from_json(ElementSeed::new(Arc::clone(&field)), &json).unwrap();
+        assert_eq!(a, b);
+        assert!(Arc::ptr_eq(&field, &b.field()));
+
+        assert!(from_json(ElementSeed::new(Arc::clone(&field)), "[1,2,3]").is_err());
+        assert!(from_json(ElementSeed::new(field), "[255,255]").is_err());
+    }
+
+    #[test]
+    fn test_z() {
+        let a = Z::from(-1234567);
+        let json = serde_json::to_string(&a).unwrap();
+        assert_eq!(json, "\"-1234567\"");
+        assert_eq!(serde_json::from_str::<Z>(&json).unwrap(), a);
+        assert!(serde_json::from_str::<Z>("\"abc\"").is_err());
+    }
+
+    #[test]
+    fn test_quadratic() {
+        let field = Arc::new(QuadraticField::new(field()));
+        let a = QuadraticField::random_element(Arc::clone(&field));
+        let json = serde_json::to_string(&a).unwrap();
+        let b = from_json(ElementSeed::new(Arc::clone(&field)), &json).unwrap();
+        assert_eq!(a, b);
+        assert!(Arc::ptr_eq(&field, &b.field));
+    }
+
+    #[test]
+    fn test_fields() {
+        let field = ZrField::new(Integer::from(ORDER));
+        let json = serde_json::to_string(&field).unwrap();
+        assert_eq!(serde_json::from_str::<ZrField>(&json).unwrap(), field);
+
+        let quadratic_field = QuadraticField::<Zr, ZrField>::new(Arc::new(field));
+        let json = serde_json::to_string(&quadratic_field).unwrap();
+        assert_eq!(serde_json::from_str::<QuadraticField<Zr, ZrField>>(&json).unwrap(), quadratic_field);
+    }
+
+    #[test]
+    fn test_invalid_fields() {
+        assert!(serde_json::from_str::<ZrField>(r#"{"order":"44496","nqr":"5"}"#).is_err());
+        assert!(serde_json::from_str::<ZrField>(r#"{"order":"44497","nqr":"4"}"#).is_err());
+        assert!(serde_json::from_str::<ZrField>(r#"{"order":"44497","nqr":"0"}"#).is_err());
+    }
+
+    #[test]
+    fn test_curve_options() {
+        let options = type_a_options();
+        let json = serde_json::to_string(&options).unwrap();
+        assert_eq!(serde_json::from_str::<CurveOptions>(&json).unwrap(), options);
+    }
+}
@@ -1,14 +1,18 @@
-use gmp::mpz::Mpz;
+use crate::pbc::Integer;
+use rand::{CryptoRng, RngCore};
 use std::fmt::Debug;
 use std::ops::*;
-use std::rc::Rc;
+use std::sync::Arc;
+use subtle::Choice;
+use super::{limbs, Wiped, Zr};
+use crate::pbc::PbcError;
 
 pub trait HasZero<E: CanBeZero> {
-    fn zero_element(self: Rc<Self>) -> E;
+    fn zero_element(self: Arc<Self>) -> E;
 }
 
 pub trait HasOne<E: CanBeOne> {
-    fn one_element(self: Rc<Self>) -> E;
+    fn one_element(self: Arc<Self>) -> E;
 }
 
 pub trait CanBeZero {
@@ -37,21 +41,141 @@ pub trait Element<T: ElementLevel>:
     + for<'a> Mul<&'a Self, Output = Self>
     + Sub<Output = Self>
     + for<'a> Sub<&'a Self, Output = Self>
+    + Div<Output = Self>
+    + for<'a> Div<&'a Self, Output = Self>
     + Neg<Output = Self>
     + for<'b> Set<&'b Self>
 {
     type FieldType: Field<Self, T>;
-    fn field(&self) -> Rc<Self::FieldType>;
+    fn field(&self) -> Arc<Self::FieldType>;
 
-    //fn inverse(&self) -> Self;
+    /// returns the multiplicative inverse, or `None` if there is none
+    fn invert(&self) -> Option<Self>;
     fn square(&self) -> Self;
     fn double(&self) -> Self;
     fn halve(&self) -> Self;
     fn is_sqrt(&self) -> bool;
     fn sqrt(&self) -> Option<(Self, Self)>;
-    //fn mulZn(&self, n: &Zr) -> Self;
-    //fn pow(&self, exp: &Mpz) -> Self;
-    //fn powZn(&self, exp: &Zr) -> Self;
+
+    /// calculates `self + self + ... + self` (n times), using double-and-add
+    /// over the words of `n`
+    fn mul_zn(&self, n: &Zr) -> Self {
+        let n = Wiped(n.to_limbs());
+        let mut result = self.field().zero_element();
+        for i in (0..64 * n.len()).rev() {
+            result = result.double();
+            if limbs::bit(&n, i) {
+                result = result + self;
+            }
+        }
+        result
+    }
+
+    /// calculates `self * self * ... * self` (exp times), using sliding
+    /// windows over the bits of `exp`. Negative exponents require `self`
+    /// to be invertible.
+    fn pow(&self, exp: &Integer) -> Self {
+        if exp < &Integer::zero() {
+            return self.invert().expect("unable to invert").pow(&-exp);
+        }
+        let bits = exp.bit_length();
+        let mut result = self.field().one_element();
+        if bits == 0 {
+            return result;
+        }
+
+        // odd powers self^1, self^3, ..., self^(2^k - 1)
+        let k = optimal_pow_window_size(bits);
+        let square = self.square();
+        let mut table = vec![self.clone()];
+        for i in 1..(1 << (k - 1)) {
+            let next = table[i - 1].clone() * &square;
+            table.push(next);
+        }
+
+        let mut i = bits;
+        while i > 0 {
+            if ! exp.tstbit(i - 1) {
+                result = result.square();
+                i -= 1;
+                continue;
+            }
+
+            // the longest window of at most k bits which ends with a one
+            let mut j = i.saturating_sub(k);
+            while ! exp.tstbit(j) {
+                j += 1;
+            }
+            let mut window = 0;
+            for bit in (j..i).rev() {
+                result = result.square();
+                window = (window << 1) | (exp.tstbit(bit) as usize);
+            }
+            result = result * &table[window >> 1];
+            i = j;
+        }
+        result
+    }
+
+    fn pow_zn(&self, exp: &Zr) -> Self {
+        self.pow(&Wiped(exp.value()))
+    }
+
+    /// like `+`, but fails instead of panicking if `other` belongs to a
+    /// different field. Types whose operators wrap the `try_*` methods
+    /// have to override them.
+    fn try_add(&self, other: &Self) -> Result<Self, PbcError> {
+        check_same_field(self, other)?;
+        Ok(self.clone() + other)
+    }
+
+    /// like `-`, but fails instead of panicking
+    fn try_sub(&self, other: &Self) -> Result<Self, PbcError> {
+        check_same_field(self, other)?;
+        Ok(self.clone() - other)
+    }
+
+    /// like `*`, but fails instead of panicking
+    fn try_mul(&self, other: &Self) -> Result<Self, PbcError> {
+        check_same_field(self, other)?;
+        Ok(self.clone() * other)
+    }
+
+    /// like `/`, but fails instead of panicking, also if `other` is not
+    /// invertible
+    fn try_div(&self, other: &Self) -> Result<Self, PbcError> {
+        check_same_field(self, other)?;
+        let inverse = other.invert().ok_or(PbcError::NotInvertible)?;
+        Ok(self.clone() * &inverse)
+    }
+
+    /// like `Set::set`, but fails instead of panicking
+    fn try_set(self, other: &Self) -> Result<Self, PbcError> {
+        check_same_field(&self, other)?;
+        Ok(self.set(other))
+    }
+}
+
+pub(crate) fn check_same_field<E: Element<T>, T: ElementLevel>(lhs: &E, rhs: &E) -> Result<(), PbcError> {
+    let (lhs, rhs) = (lhs.field(), rhs.field());
+    if Arc::ptr_eq(&lhs, &rhs) || lhs == rhs {
+        Ok(())
+    } else {
+        Err(PbcError::FieldMismatch)
+    }
+}
+
+/// window size used by PBC's `element_pow_wind`
+fn optimal_pow_window_size(bits: usize) -> usize {
+    match bits {
+        b if b > 9065 => 8,
+        b if b > 3529 => 7,
+        b if b > 1324 => 6,
+        b if b > 474 => 5,
+        b if b > 157 => 4,
+        b if b > 47 => 3,
+        _ => 2,
+    }
 }
 
 pub trait SuperElement<E, T> : Element<ComplexElement> 
@@ -59,6 +183,24 @@ where E: Element<T>, T: ElementLevel {
     type SubType: Element<T>;
 }
 
+/// like `subtle::ConditionallySelectable`, which requires `Copy`, for
+/// elements which own their values. Implementations must not branch on
+/// `choice`.
+pub trait ConditionallyAssignable {
+    /// assigns `other` to `self` if `choice` is set
+    fn conditional_assign(&mut self, other: &Self, choice: Choice);
+
+    /// swaps `a` and `b` if `choice` is set
+    fn conditional_swap(a: &mut Self, b: &mut Self, choice: Choice)
+    where
+        Self: Clone,
+    {
+        let previous = a.clone();
+        a.conditional_assign(b, choice);
+        b.conditional_assign(&previous, choice);
+    }
+}
+
 pub trait Set<V>
 {
     fn set(self, value: V) -> Self;
@@ -69,14 +211,20 @@ where
     T: ElementLevel,
 {
     //type ElementType: Element<T>;
-    fn random_element(self: Rc<Self>) -> E;
+
+    /// uses `rand::thread_rng()` as source of randomness
+    fn random_element(self: Arc<Self>) -> E {
+        self.random_element_with(&mut rand::thread_rng())
+    }
+
+    fn random_element_with<R: RngCore + CryptoRng>(self: Arc<Self>, rng: &mut R) -> E;
 }
 
 pub trait FiniteField<E: Element<T>, T>: Field<E, T>
 where
     T: ElementLevel,
 {
-    fn order(&self) -> &Mpz;
+    fn order(&self) -> &Integer;
 }
 
 pub trait FieldOver<E, F, E2, G, T>: Field<E, ComplexElement>
@@ -86,12 +234,12 @@ where
     E2: Element<T>,
     T: ElementLevel,
 {
-    fn target_field(&self) -> Rc<G>;
+    fn target_field(&self) -> Arc<G>;
 }
 
 pub trait HasNqr<E: Element<T>, T>: Field<E, T>
 where
     T: ElementLevel,
 {
-    fn nqr(field: Rc<Self>) -> E;
+    fn nqr(field: Arc<Self>) -> E;
 }
@@ -0,0 +1,91 @@
+use std::ops::{Deref, DerefMut};
+use crate::pbc::Integer;
+
+/// a temporary copy of a secret value, e.g. the decoded value of a `Zr`.
+/// With the feature `zeroize`, it is overwritten when it is dropped.
+pub(crate) struct Wiped<T: Wipe>(pub T);
+
+pub(crate) trait Wipe {
+    fn wipe(&mut self);
+}
+
+impl Wipe for Integer {
+    fn wipe(&mut self) {
+        #[cfg(feature = "zeroize")]
+        crate::pbc::wipe(self);
+    }
+}
+
+impl Wipe for Vec<u64> {
+    fn wipe(&mut self) {
+        #[cfg(feature = "zeroize")]
+        zeroize::Zeroize::zeroize(&mut self[..]);
+    }
+}
+
+impl<T: Wipe> Deref for Wiped<T> {
+    type Target = T;
+    fn deref(&self) -> &T { &self.0 }
+}
+
+impl<T: Wipe> DerefMut for Wiped<T> {
+    fn deref_mut(&mut self) -> &mut T { &mut self.0 }
+}
+
+impl<T: Wipe> Drop for Wiped<T> {
+    fn drop(&mut self) {
+        self.0.wipe();
+        #[cfg(test)]
+        tests::WIPED.with(|count| count.set(count.get() + 1));
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::pbc::*;
+    use crate::pbc::testlib::algebra::ORDER;
+    use std::cell::Cell;
+    use std::sync::Arc;
+
+    thread_local! {
+        /// the number of dropped guards of the current thread
+        pub(super) static WIPED: Cell<usize> = const { Cell::new(0) };
+    }
+
+    /// the number of guards which `f` drops
+    fn wiped_by<R>(f: impl FnOnce() -> R) -> usize {
+        let before = WIPED.with(Cell::get);
+        f();
+        WIPED.with(Cell::get) - before
+    }
+
+    #[test]
+    fn test_mul_zn() {
+        let field = Arc::new(ZrField::new(Integer::from(ORDER)));
+        let n = Zr::new(Integer::from(31337), Arc::clone(&field));
+        let quadratic = QuadraticField::random_element(Arc::new(QuadraticField::new(Arc::clone(&field))));
+        assert!(wiped_by(|| quadratic.mul_zn(&n)) > 0);
+
+        let curve_field = Arc::new(CurveField::type_a(&TypeACurveGenerator::new(24, 64).generate()));
+        let point = CurveField::random_element(curve_field);
+        assert!(wiped_by(|| point.mul_zn(&n)) > 0);
+
+        // the decoded value of `n` and its reduction
+        let other = Zr::new(Integer::from(5), Arc::new(ZrField::new(Integer::from(44483))));
+        assert!(wiped_by(|| other.mul_zn(&n)) >= 2);
+    }
+
+    #[test]
+    fn test_wipe() {
+        let mut value = Wiped(Integer::from(31337));
+        value.wipe();
+        let mut words = Wiped(vec![1_u64, 2, 3]);
+        words.wipe();
+        if cfg!(feature = "zeroize") {
+            assert!(value.is_zero());
+            assert_eq!(*words, vec![0, 0, 0]);
+        }
+        assert_eq!(wiped_by(|| drop(value)), 1);
+    }
+}
@@ -1,52 +1,56 @@
-use gmp::mpz::{Mpz};
+use crate::pbc::{Integer, PbcError};
 use num_traits::*;
 use duplicate::duplicate;
 use crate::pbc::elements::traits::*;
 use super::ZField;
-use std::rc::Rc;
+use std::sync::Arc;
 use std::ops;
 use std::ops::Neg;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Z {
-    value: Mpz,
-    field: Rc<ZField>
+    pub(super) value: Integer,
+    field: Arc<ZField>
 }
 
 impl Z {
-    pub fn new(d: Mpz) -> Z {
-        Z { value: d, field: Rc::new(ZField::new()) }
+    pub fn new(d: Integer) -> Z {
+        Z { value: d, field: Arc::new(ZField::new()) }
+    }
+
+    pub fn value(&self) -> &Integer {
+        &self.value
     }
 }
 
 /// takes ownership of `op`
-impl From<Mpz> for Z {
-    fn from(op: Mpz) -> Self {
+impl From<Integer> for Z {
+    fn from(op: Integer) -> Self {
         Self {
             value: op,
-            field: Rc::new(ZField::new())
+            field: Arc::new(ZField::new())
         }
     }
 }
 
 /// creates a copy of `op`
-impl<'a> From<&'a Mpz> for Z {
-    fn from(op: &'a Mpz) -> Self {
+impl<'a> From<&'a Integer> for Z {
+    fn from(op: &'a Integer) -> Self {
         Self {
             value: op.clone(),
-            field: Rc::new(ZField::new())
+            field: Arc::new(ZField::new())
         }
     }
 }
 
 #[duplicate(int_type; [i32]; [i64]; [u32]; [u64]; )]
-impl From<int_type> for Z { fn from(op: int_type) -> Self { Self::from(Mpz::from(op)) } }
+impl From<int_type> for Z { fn from(op: int_type) -> Self { Self::from(Integer::from(op)) } }
 /*
 impl Num for Z {
     type FromStrRadixErr = ParseMpzError;
 
     fn from_str_radix(str: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
-        match Mpz::from_str_radix(str, radix as u8){
+        match Integer::from_str_radix(str, radix as u8){
             Ok(value) => Ok(Z::from(value)),
             Err(why) => Err(why)
         }
@@ -60,7 +64,8 @@ macro_rules! add_operators {
         )+
     };
 }
-add_operators!(+-*/%);
+add_operators!(+-*%);
+impl_op_ex!(/ |lhs:&Z, rhs:&Z | -> Z { lhs.try_div(rhs).unwrap_or_else(|e| panic!("{}", e)) });
 impl_op!(* |lhs:Z, rhs:i64 | -> Z {Z::new (&lhs.value * rhs)});
 impl_op!(* |lhs:Z, rhs:u64 | -> Z {Z::new (&lhs.value * rhs)});
 
@@ -74,7 +79,7 @@ impl CanBeZero for Z {
 
 impl Neg for Z {
     type Output = Z;
-    fn neg(self) -> Self::Output { Self::from(self.value.neg()) }
+    fn neg(self) -> Self::Output { Self::from(-&self.value) }
 }
 /*
 impl Signed for Z {
@@ -82,7 +87,7 @@ impl Signed for Z {
 
     fn abs_sub(&self, rhs: &Self) -> Self {
         let d = &self.value - &rhs.value;
-        if d < Mpz::zero() {
+        if d < Integer::zero() {
             Self::zero()
         } else {
             Z { value: d }
@@ -97,14 +102,13 @@ impl Signed for Z {
         }
     }
 
-    fn is_positive(&self) -> bool { self.value.gt(&Mpz::zero()) }
-    fn is_negative(&self) -> bool { self.value.lt(&Mpz::zero()) }
+    fn is_positive(&self) -> bool { self.value.gt(&Integer::zero()) }
+    fn is_negative(&self) -> bool { self.value.lt(&Integer::zero()) }
 }
 */
 
-impl<'b> Set<&'b Z> for Z {
+impl Set<&Z> for Z {
     fn set(mut self, other: &Self) -> Self {
-        assert_eq!(self.field(), other.field());
         self.value = other.value.clone();
         self
     }
@@ -113,8 +117,16 @@ impl<'b> Set<&'b Z> for Z {
 impl Element<AtomicElement> for Z {
     type FieldType = ZField;
 
-    fn field(&self) -> Rc<Self::FieldType> {
-        Rc::clone(&self.field)
+    fn field(&self) -> Arc<Self::FieldType> {
+        Arc::clone(&self.field)
+    }
+    /// only 1 and -1 have a multiplicative inverse in Z
+    fn invert(&self) -> Option<Self> {
+        if self.value.abs().is_one() {
+            Some(self.clone())
+        } else {
+            None
+        }
     }
     fn square(&self) -> Self {self * self}
     fn double(&self) -> Self {Self::from(&self.value << 1) }
@@ -132,13 +144,22 @@ impl Element<AtomicElement> for Z {
             Self::from(s2)
         ))
     }
+
+    /// integer division, which rounds towards zero like `/` and only fails
+    /// for a zero divisor
+    fn try_div(&self, other: &Self) -> Result<Self, PbcError> {
+        if other.is_zero() {
+            return Err(PbcError::NotInvertible);
+        }
+        Ok(Z::new(&self.value / &other.value))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::pbc::elements::ZField;
-    use std::rc::Rc;
+    use std::sync::Arc;
     use std::ops::*;
     use crate::pbc::testlib::algebra::*;
     use crate::test_one;
@@ -148,8 +169,9 @@ mod tests {
     use crate::test_double_and_halve;
     use crate::test_distributivity;
     use crate::test_square_and_sqrt;
+    use crate::test_pow;
 
-    fn field() -> Rc<ZField> { Rc::new(ZField::new()) }
+    fn field() -> Arc<ZField> { Arc::new(ZField::new()) }
     
     test_one!(Z, ZField, field());
     test_zero!(Z, ZField, field());
@@ -160,4 +182,19 @@ mod tests {
     test_associativity!(Z, add, ZField, field());
     test_associativity!(Z, mul, ZField, field());
     test_distributivity!(Z, add, mul, ZField, field());
+    test_pow!(Z, ZField, field());
+
+    #[test]
+    fn test_try_div() {
+        // rounds towards zero
+        for (a, b, quotient) in [(6, 3, 2), (7, 2, 3), (-7, 2, -3), (7, -2, -3), (0, 5, 0)] {
+            let (a, b) = (Z::from(a), Z::from(b));
+            assert_eq!(a.try_div(&b), Ok(Z::from(quotient)));
+            assert_eq!(a.try_div(&b), Ok(&a / &b));
+        }
+        assert_eq!(Z::from(6).try_div(&Z::from(0)), Err(PbcError::NotInvertible));
+
+        let error = std::panic::catch_unwind(|| Z::from(6) / Z::from(0)).unwrap_err();
+        assert_eq!(error.downcast_ref::<String>().unwrap(), &PbcError::NotInvertible.to_string());
+    }
 }
\ No newline at end of file
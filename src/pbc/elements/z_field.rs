@@ -1,8 +1,8 @@
-use gmp::mpz::Mpz;
+use crate::pbc::{BigInteger, Integer};
 use super::Z;
 use rand::*;
 use crate::pbc::*;
-use std::rc::Rc;
+use std::sync::Arc;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct ZField();
@@ -11,23 +11,69 @@ impl ZField {
     pub fn new() -> ZField {
         ZField {}
     }
+
+    /// returns a uniformly distributed value in [0, bound), which must be
+    /// positive
+    pub fn random_below_with<R: RngCore + CryptoRng>(&self, rng: &mut R, bound: &Integer) -> Z {
+        Z::new(Integer::urandom(rng, bound))
+    }
+
+    /// returns a uniformly distributed value with at most `bits` bits
+    pub fn random_bits_with<R: RngCore + CryptoRng>(&self, rng: &mut R, bits: usize) -> Z {
+        let mut bound = Integer::zero();
+        bound.setbit(bits);
+        self.random_below_with(rng, &bound)
+    }
+}
+
+impl Default for ZField {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl HasOne<Z> for ZField {
-    fn one_element(self: Rc<Self>) -> Z {
+    fn one_element(self: Arc<Self>) -> Z {
         Z::from(1)
     }
 }
 
 impl HasZero<Z> for ZField {
-    fn zero_element(self: Rc<Self>) -> Z {
+    fn zero_element(self: Arc<Self>) -> Z {
         Z::from(0)
     }
 }
 
 impl Field<Z, AtomicElement> for ZField {
-    fn random_element(self: Rc<Self>) -> Z {
-        let mut rng1 = rand::thread_rng();
-        Z::new(Mpz::from(rng1.next_u64()))
+    /// there is no uniform distribution over all integers, so the values
+    /// are taken from [0, 2⁶⁴). Use `random_below_with` or
+    /// `random_bits_with` for other ranges.
+    fn random_element_with<R: RngCore + CryptoRng>(self: Arc<Self>, rng: &mut R) -> Z {
+        self.random_bits_with(rng, 64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn test_random_below_with() {
+        let field = ZField::new();
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut bound = Integer::zero();
+        bound.setbit(200);
+        let bound = bound - Integer::from(7);
+
+        let values: Vec<Z> = (0..20).map(|_| field.random_below_with(&mut rng, &bound)).collect();
+        assert!(values.iter().all(|z| z.value() >= &Integer::zero() && z.value() < &bound));
+        assert!(values.iter().any(|z| z.value().bit_length() > 64));
+
+        let a = field.random_bits_with(&mut StdRng::seed_from_u64(2), 300);
+        let b = field.random_bits_with(&mut StdRng::seed_from_u64(2), 300);
+        assert_eq!(a, b);
+        assert!(a.value().bit_length() <= 300);
     }
 }
\ No newline at end of file
@@ -0,0 +1,119 @@
+use zeroize::{Zeroize, ZeroizeOnDrop};
+use crate::pbc::wipe;
+use super::traits::*;
+use super::{FieldParams, Fp, Quadratic, SecretZr, Z, Zr};
+
+/// overwrites the limbs of the value, which leaves zero in the field.
+/// Decoded copies of the value are wiped by `Wiped`, but the words which
+/// the arithmetic keeps on the stack are not covered.
+impl Zeroize for Zr {
+    fn zeroize(&mut self) {
+        self.value[..].zeroize();
+    }
+}
+
+impl Drop for Zr {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for Zr {}
+
+/// overwrites the limbs of the value, which leaves zero
+impl Zeroize for Z {
+    fn zeroize(&mut self) {
+        wipe(&mut self.value);
+    }
+}
+
+impl Drop for Z {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for Z {}
+
+impl<E, F> Zeroize for Quadratic<E, F>
+where
+    E: Element<AtomicElement> + Zeroize,
+    F: HasZero<E> + HasNqr<E, AtomicElement>,
+{
+    fn zeroize(&mut self) {
+        self.x.zeroize();
+        self.y.zeroize();
+    }
+}
+
+/// both coordinates zeroize themselves
+impl<E, F> ZeroizeOnDrop for Quadratic<E, F>
+where
+    E: Element<AtomicElement> + ZeroizeOnDrop,
+    F: HasZero<E> + HasNqr<E, AtomicElement>,
+{}
+
+/// `Fp` is `Copy` and cannot be zeroized on drop, so copies have to be
+/// zeroized explicitly
+impl<const LIMBS: usize, P: FieldParams<LIMBS>> Zeroize for Fp<LIMBS, P> {
+    fn zeroize(&mut self) {
+        self.limbs.zeroize();
+    }
+}
+
+impl Zeroize for SecretZr {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+/// the inner `Zr` zeroizes itself
+impl ZeroizeOnDrop for SecretZr {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pbc::*;
+    use crate::pbc::testlib::algebra::ORDER;
+    use std::sync::Arc;
+
+    fn field() -> Arc<ZrField> { Arc::new(ZrField::new(Integer::from(ORDER))) }
+
+    #[test]
+    fn test_zr() {
+        let mut a = ZrField::random_element(field());
+        a.zeroize();
+        assert!(a.is_zero());
+    }
+
+    #[test]
+    fn test_z() {
+        let mut a = Z::from(-1234567890123_i64);
+        a.zeroize();
+        assert!(a.is_zero());
+    }
+
+    #[test]
+    fn test_quadratic() {
+        let field = Arc::new(QuadraticField::new(field()));
+        let mut a = QuadraticField::random_element(field);
+        a.zeroize();
+        assert!(a.is_zero());
+    }
+
+    #[test]
+    fn test_secret_zr() {
+        let mut secret = SecretZr::new(ZrField::random_element(field()));
+        secret.zeroize();
+        assert!(secret.expose_secret().is_zero());
+    }
+
+    #[test]
+    fn test_zeroize_on_drop() {
+        fn assert_zeroize_on_drop<T: ZeroizeOnDrop>() {}
+        assert_zeroize_on_drop::<Zr>();
+        assert_zeroize_on_drop::<Z>();
+        assert_zeroize_on_drop::<Quadratic<Zr, ZrField>>();
+        assert_zeroize_on_drop::<SecretZr>();
+    }
+}
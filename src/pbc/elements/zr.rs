@@ -1,184 +1,363 @@
-use gmp::mpz::Mpz;
+use crate::pbc::Integer;
 use std::ops;
-use num_traits::{One, Zero};
 use crate::pbc::elements::traits::*;
+use std::fmt;
 use std::ops::Neg;
-use std::rc::Rc;
-use super::ZrField;
+use std::sync::Arc;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+use super::{ZrField, Wiped, BytesError, ByteLength, ToBytes, FromBytes, check_length};
+use crate::pbc::PbcError;
 
-#[derive(Debug, Clone, PartialEq)]
+/// element of a prime field, which is stored in Montgomery representation
+/// value·R mod order as 64 bit words. Conversions only happen in `new`,
+/// `value` and the serialization.
+/// The arithmetic does not branch on the values, apart from `invert`,
+/// `sqrt`, `legendre` and exponents of `pow`. The `*_ct` methods replace
+/// them for secret values.
+#[derive(Clone)]
 pub struct Zr {
-    value: Mpz,
-    field: Rc<ZrField>
+    pub(super) value: Vec<u64>,
+    field: Arc<ZrField>
+}
+
+/// the value is redacted, unless it is requested with the alternate flag
+/// `{:#?}`
+impl fmt::Debug for Zr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let alternate = f.alternate();
+        let mut output = f.debug_struct("Zr");
+        if alternate {
+            output.field("value", &*Wiped(self.value()));
+        } else {
+            output.field("value", &format_args!("[REDACTED]"));
+        }
+        output.field("field", &self.field).finish()
+    }
 }
 
 impl Element<AtomicElement> for Zr {
     type FieldType = ZrField;
 
-    fn field(&self) -> Rc<Self::FieldType> {
-        Rc::clone(&self.field)
+    fn field(&self) -> Arc<Self::FieldType> {
+        Arc::clone(&self.field)
     }
 
     fn double(&self) -> Self {
-        Zr::new(
-            self.value() * Mpz::from(2),
-            Rc::clone(&self.field)
-        )
+        self.with_value(self.field.montgomery().add(&self.value, &self.value))
     }
 
     fn halve(&self) -> Self {
-        Zr::new(
-            self.value() * &Self::FieldType::two_inverse(self.field.as_ref()),
-            Rc::clone(&self.field)
-        )
+        self.with_value(self.field.montgomery().halve(&self.value))
+    }
+
+    fn invert(&self) -> Option<Self> {
+        self.field.montgomery().invert(&self.value).map(|value| self.with_value(value))
     }
 
-    fn square(&self) -> Self {self * self }
+    fn square(&self) -> Self {
+        self.with_value(self.field.montgomery().square(&self.value))
+    }
 
     fn is_sqrt(&self) -> bool {
+        self.is_zero() || self.euler_criterion().is_one()
+    }
+
+    // Tonelli-Shanks algorithm
+    fn sqrt(&self) -> Option<(Self,Self)> {
         if self.is_zero() {
-            true
-        } else {
-            self.legendre() == Mpz::one()
+            return Some((self.clone(), self.clone()));
+        }
+        if ! self.is_sqrt() {
+            return None;
         }
+
+        let (s, odd) = self.two_adicity();
+        let mut c = ZrField::nqr(self.field()).pow(&odd);
+        let mut t = self.pow(&odd);
+        // self^((odd + 1) / 2)
+        let mut r = self.pow(&(odd >> 1)) * self;
+        let mut m = s;
+        while ! t.is_one() {
+            let mut i = 1;
+            let mut z = t.square();
+            while ! z.is_one() {
+                z = z.square();
+                i += 1;
+            }
+            let mut b = c;
+            for _ in 0..(m - i - 1) {
+                b = b.square();
+            }
+            r = r * &b;
+            c = b.square();
+            t = t * &c;
+            m = i;
+        }
+        let neg_r = -r.clone();
+        Some((r, neg_r))
     }
 
-    fn sqrt(&self) -> Option<(Self,Self)> {
-        Zr::sqrt(self.field(), self.value())
+    fn pow(&self, exp: &Integer) -> Self {
+        if exp < &Integer::zero() {
+            return self.invert().expect("unable to invert").pow(&-exp);
+        }
+        self.with_value(self.field.montgomery().pow(&self.value, exp))
+    }
+
+    /// multiplies in the Montgomery representation, if `n` belongs to the
+    /// same field. Otherwise the decoded value of `n` is reduced into this
+    /// field, both copies are wiped.
+    fn mul_zn(&self, n: &Zr) -> Self {
+        match Zr::common_field(self, n) {
+            Some(_) => self * n,
+            None => {
+                let n = Wiped(n.value());
+                self * Zr::new(n.modulus(self.field.order()), Arc::clone(&self.field))
+            }
+        }
+    }
+
+    fn try_add(&self, other: &Self) -> Result<Self, PbcError> {
+        let field = Zr::common_field(self, other).ok_or(PbcError::FieldMismatch)?;
+        let value = field.montgomery().add(&self.value, &other.value);
+        Ok(Zr { value, field })
+    }
+
+    fn try_sub(&self, other: &Self) -> Result<Self, PbcError> {
+        let field = Zr::common_field(self, other).ok_or(PbcError::FieldMismatch)?;
+        let value = field.montgomery().sub(&self.value, &other.value);
+        Ok(Zr { value, field })
+    }
+
+    fn try_mul(&self, other: &Self) -> Result<Self, PbcError> {
+        let field = Zr::common_field(self, other).ok_or(PbcError::FieldMismatch)?;
+        let value = field.montgomery().mul(&self.value, &other.value);
+        Ok(Zr { value, field })
+    }
+
+    fn try_set(mut self, other: &Self) -> Result<Self, PbcError> {
+        Zr::common_field(&self, other).ok_or(PbcError::FieldMismatch)?;
+        self.value.clone_from(&other.value);
+        Ok(self)
     }
 }
 
 
 impl<'b> Set<&'b Zr> for Zr {
-    fn set(mut self, other: &'b Self) -> Self {
-        assert_eq!(self.field(), other.field());
-        self.value = other.value.clone();
-        self
+    fn set(self, other: &'b Self) -> Self {
+        self.try_set(other).unwrap_or_else(|e| panic!("{}", e))
     }
 }
 
 impl Zr {
-    pub fn new(value: Mpz, field: Rc<ZrField>) -> Zr {
-        let value = value % field.order();
+    /// the consumed `value` is wiped with the feature `zeroize`
+    pub fn new(value: Integer, field: Arc<ZrField>) -> Zr {
+        let value = Wiped(value);
+        let value = if *value >= Integer::zero() && &*value < field.order() {
+            field.montgomery().encode(&value)
+        } else {
+            field.montgomery().encode(&Wiped(value.modulus(field.order())))
+        };
         Self {
             value,
             field
         }
     }
 
-    pub fn value(&self) -> &Mpz {
-        &self.value
+    /// the value in [0, order), converted from the Montgomery representation
+    pub fn value(&self) -> Integer {
+        self.field.montgomery().decode(&self.value)
+    }
+
+    /// the value in [0, order) as little-endian words, without the
+    /// conversion into an `Integer`
+    pub fn to_limbs(&self) -> Vec<u64> {
+        self.field.montgomery().to_canonical(&self.value)
+    }
+
+    /// creates an element of the same field from a value in Montgomery
+    /// representation
+    fn with_value(&self, value: Vec<u64>) -> Zr {
+        Zr { value, field: Arc::clone(&self.field) }
+    }
+
+    /// creates an element from its Montgomery representation
+    pub(super) fn from_montgomery(value: Vec<u64>, field: Arc<ZrField>) -> Zr {
+        Zr { value, field }
     }
 
-    fn common_field(z1: &Zr, z2: &Zr) -> Option<Rc<ZrField>> {
-        if z1.field == z2.field {
-            Some(Rc::clone(&z1.field))
+    fn common_field(z1: &Zr, z2: &Zr) -> Option<Arc<ZrField>> {
+        if Arc::ptr_eq(&z1.field, &z2.field) || z1.field == z2.field {
+            Some(Arc::clone(&z1.field))
         } else {
             None
         }
     }
 
-    pub fn legendre(&self) -> Mpz {
+    /// order - 1 = odd·2^s
+    fn two_adicity(&self) -> (usize, Integer) {
+        let order_minus_one = self.field.order() - Integer::one();
+        let mut s = 0;
+        while ! order_minus_one.tstbit(s) {
+            s += 1;
+        }
+        (s, order_minus_one >> s)
+    }
+
+    /// self^((order - 1) / 2), which is one for squares and -1 for
+    /// nonresidues
+    fn euler_criterion(&self) -> Zr {
+        self.pow(&((self.field.order() - Integer::one()) >> 1))
+    }
+
+    /// returns 0 for zero, 1 for squares and order - 1 for nonresidues
+    pub fn legendre(&self) -> Integer {
         if self.is_zero() {
-            Mpz::from(0)
-        } else if self.is_one() {
-            Mpz::from(1)
+            Integer::from(0)
+        } else if self.euler_criterion().is_one() {
+            Integer::from(1)
         } else {
-            self.field.legendre(&self.value())
+            self.field.order() - Integer::one()
         }
     }
 
-    // Tonelli-Shanks algorithm
-    pub fn sqrt(field: Rc<ZrField>, n: &Mpz) -> Option<(Zr,Zr)> {
-        // for better readability
-        let p = field.order();
- 
-        if ! field.legendre(n).is_one() {
-            return None;
+    /// calculates `self^exp` with a Montgomery ladder like `Fp::pow_ct`,
+    /// which performs the same operations for all exponents of
+    /// `64·exp.len()` bits, e.g. the words returned by `to_limbs` of a
+    /// secret scalar
+    pub fn pow_ct(&self, exp: &[u64]) -> Zr {
+        // invariant: r1 = r0·self
+        let mut r0 = ZrField::one_element(Arc::clone(&self.field));
+        let mut r1 = self.clone();
+        for i in (0..64 * exp.len()).rev() {
+            let bit = Choice::from(((exp[i / 64] >> (i % 64)) & 1) as u8);
+            Zr::conditional_swap(&mut r0, &mut r1, bit);
+            r1 = &r0 * &r1;
+            r0 = r0.square();
+            Zr::conditional_swap(&mut r0, &mut r1, bit);
         }
-        
-        let mut q = p - 1;
-        let mut s = Mpz::zero();
-        while ((&q) & &Mpz::one()).is_zero() {
-            s += 1;
-            q >>= 1
-        }
-        
-        if s.is_one() {
-            let exp = (p+1)/4;
-            let r1 = n.powm(&exp, p);
-            let res2 = Zr::new(p - (&r1), field.clone());
-            let res1 = Zr::new(r1,        field);
-            return Some((res1, res2));
-        }
-        
-        let mut z = Mpz::from(2);
-        while field.legendre(&z) != p-1 {
-            z += 1
-        }
-        let mut c = z.powm(&q, p);
-        
-        let mut r = n.powm(&(((&q)+1)/2), p);
-        let mut t = n.powm(&q, p);
-        let mut m = s;
-        
-        loop {
-            if t.is_one() {
-                let res2 = Zr::new(p - &r, field.clone());
-                let res1 = Zr::new(r,      field);
-                return Some((res1, res2));
-            }
-            
-            let mut i = Mpz::zero();
-            let mut z = t.clone();
-            let mut b = c.clone();
-            while !z.is_one() && &i < &(&m - 1) {
-                z = (&z) * (&z) % p;
-                i += 1;
-            }
-            let mut e = &m - &i - 1;
-            while &e > &Mpz::zero() {
-                b = (&b) * (&b) % p;
-                e -= 1;
+        r0
+    }
+
+    /// like `invert`, but without branching on the value, i.e.
+    /// self^(order - 2)
+    pub fn invert_ct(&self) -> CtOption<Zr> {
+        let inverse = self.pow(&(self.field.order() - Integer::from(2)));
+        CtOption::new(inverse, !self.is_zero_ct())
+    }
+
+    /// like `is_sqrt` and `legendre`, but without branching on the value
+    pub fn is_sqrt_ct(&self) -> Choice {
+        self.euler_criterion().ct_eq(&ZrField::one_element(Arc::clone(&self.field))) | self.is_zero_ct()
+    }
+
+    /// one of the square roots, using the variant of Tonelli-Shanks in
+    /// appendix I.4 of RFC 9380, whose operations only depend on the order
+    pub fn sqrt_ct(&self) -> CtOption<Zr> {
+        let (s, odd) = self.two_adicity();
+        let one = ZrField::one_element(Arc::clone(&self.field));
+        let mut c = ZrField::nqr(self.field()).pow(&odd);
+        // self^((odd - 1) / 2)
+        let mut z = self.pow(&(odd >> 1));
+        let mut t = z.square() * self;
+        z = z * self;
+        let mut b = t.clone();
+        for i in (2..=s).rev() {
+            for _ in 2..i {
+                b = b.square();
             }
-            r = (&r) * (&b) % p;
-            c = (&b) * (&b) % p;
-            t = (&t) * (&c) % p;
-            m = i;
+            let is_one = b.ct_eq(&one);
+            z.conditional_assign(&(&z * &c), !is_one);
+            c = c.square();
+            t.conditional_assign(&(&t * &c), !is_one);
+            b = t.clone();
+        }
+        let is_root = z.square().ct_eq(self);
+        CtOption::new(z, is_root)
+    }
+
+    fn is_zero_ct(&self) -> Choice {
+        self.value.iter().fold(0, |acc, limb| acc | limb).ct_eq(&0)
+    }
+
+    /// the square roots of `n` in the field
+    pub fn sqrt(field: Arc<ZrField>, n: &Integer) -> Option<(Zr,Zr)> {
+        Element::sqrt(&Zr::new(n.clone(), field))
+    }
+}
+
+/// fixed-width big-endian representation
+impl ToBytes for Zr {
+    fn length_in_bytes(&self) -> usize {
+        self.field.length_in_bytes()
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let words = Wiped(self.to_limbs());
+        (0..self.length_in_bytes()).rev().map(|i| (words[i / 8] >> (8 * (i % 8))) as u8).collect()
+    }
+}
+
+impl FromBytes<ZrField> for Zr {
+    fn from_bytes(field: Arc<ZrField>, bytes: &[u8]) -> Result<Self, BytesError> {
+        check_length(field.as_ref(), bytes)?;
+        let value = Integer::from(bytes);
+        if &value >= field.order() {
+            return Err(BytesError::OutOfRange);
         }
+        Ok(Zr::new(value, field))
     }
 }
 
 impl CanBeOne for Zr {
-    fn is_one(&self) -> bool { self.value.is_one() }
+    fn is_one(&self) -> bool { self.value[..].ct_eq(self.field.montgomery().one()).into() }
+}
+
+/// the fields are public, only the values are compared in constant time
+impl ConstantTimeEq for Zr {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        Choice::from(Zr::common_field(self, other).is_some() as u8) & self.value[..].ct_eq(&other.value[..])
+    }
+}
+
+impl PartialEq for Zr {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+/// both elements have to belong to the same field
+impl ConditionallyAssignable for Zr {
+    fn conditional_assign(&mut self, other: &Self, choice: Choice) {
+        if Zr::common_field(self, other).is_none() {
+            panic!("{}", PbcError::FieldMismatch);
+        }
+        for (limb, other) in self.value.iter_mut().zip(&other.value) {
+            limb.conditional_assign(other, choice);
+        }
+    }
 }
 
 impl CanBeZero for Zr {
-    fn is_zero(&self) -> bool { self.value.is_zero() }
+    fn is_zero(&self) -> bool { self.is_zero_ct().into() }
 }
 
 macro_rules! add_operators {
-    ($($op:tt)+) => {
+    ($($op:tt $method:ident),+) => {
         $(
             impl_op_ex!($op |lhs:&Zr, rhs:&Zr | -> Zr {
-                let field = Zr::common_field(&lhs, &rhs).unwrap_or_else(|| panic!("unable to calculate, because fields for lhs and rhs are different (lhs ∈ {{0..{}, nqr={:?}}}, rhs ∈ {{0..{}, nqr={:?}}})", lhs.field.order(), ZrField::nqr(Rc::clone(&lhs.field)), rhs.field.order(), ZrField::nqr(Rc::clone(&rhs.field))));
-                Zr::new(lhs.value() $op rhs.value(), field)
+                lhs.$method(rhs).unwrap_or_else(|e| panic!("{}", e))
             });
         )+
     };
 }
-add_operators!(+-*);
-
-impl_op_ex!(/ |lhs:&Zr, rhs:&Zr | -> Zr {
-    let field = Zr::common_field(lhs, rhs).expect("unable to calculate");
-    Zr::new(lhs.value() * &field.inverse_of(&rhs.value()), field)
-});
+add_operators!(+ try_add, - try_sub, * try_mul, / try_div);
 
 
 impl Neg for Zr {
     type Output = Zr;
     fn neg(self) -> Self::Output {
-        Zr::new(self.field.order() - Mpz::one(), Rc::clone(&self.field))
+        self.with_value(self.field.montgomery().neg(&self.value))
     }
 }
 
@@ -186,6 +365,7 @@ impl Neg for Zr {
 mod tests {
     use super::*;
     use std::ops::*;
+    use subtle::Choice;
     use crate::pbc::testlib::algebra::*;
     use crate::test_one;
     use crate::test_zero;
@@ -195,8 +375,14 @@ mod tests {
     use crate::test_distributivity;
     use crate::test_square_and_sqrt;
     use crate::test_nqr;
+    use crate::test_neg;
+    use crate::test_pow;
+    use crate::test_invert;
+    use crate::test_bytes;
+    use crate::test_pow_preprocessed;
+    use crate::test_multi_exp;
 
-    fn field() -> Rc<ZrField> { Rc::new(ZrField::new(Mpz::from(ORDER))) }
+    fn field() -> Arc<ZrField> { Arc::new(ZrField::new(Integer::from(ORDER))) }
     
     test_one!(Zr, ZrField, field());
     test_zero!(Zr, ZrField, field());
@@ -208,4 +394,118 @@ mod tests {
     test_associativity!(Zr, mul, ZrField, field());
     test_distributivity!(Zr, add, mul, ZrField, field());
     test_nqr!(Zr, ZrField, field());
-}
\ No newline at end of file
+    test_neg!(Zr, ZrField, field());
+    test_pow!(Zr, ZrField, field());
+    test_invert!(Zr, ZrField, field());
+    test_bytes!(Zr, ZrField, field());
+    test_pow_preprocessed!(Zr, ZrField, field());
+    test_multi_exp!(Zr, ZrField, field());
+
+    #[test]
+    fn test_invert_zero() {
+        let zero: Zr = ZrField::zero_element(field());
+        assert_eq!(zero.invert(), None);
+    }
+
+    #[test]
+    fn test_bytes_layout() {
+        let field = field();
+        assert_eq!(field.length_in_bytes(), 2);
+        assert_eq!(Zr::new(Integer::from(258), Arc::clone(&field)).to_bytes(), vec![1, 2]);
+        assert_eq!(Zr::new(Integer::from(7), Arc::clone(&field)).to_bytes(), vec![0, 7]);
+        assert_eq!(Zr::new(Integer::from(258), Arc::clone(&field)).to_limbs(), vec![258]);
+        assert_eq!(Zr::from_bytes(Arc::clone(&field), &[0, 7]), Ok(Zr::new(Integer::from(7), Arc::clone(&field))));
+
+        let order: Vec<u8> = Vec::from(field.order());
+        assert_eq!(Zr::from_bytes(Arc::clone(&field), &order), Err(BytesError::OutOfRange));
+        assert_eq!(Zr::from_bytes(field, &[0xff, 0xff]), Err(BytesError::OutOfRange));
+    }
+
+    #[test]
+    fn test_try_operations() {
+        let field = field();
+        let other_field = Arc::new(ZrField::new(Integer::from(44483)));
+        let a = ZrField::random_element(Arc::clone(&field));
+        let b = ZrField::random_element(Arc::clone(&field));
+        let c = ZrField::random_element(other_field);
+
+        assert_eq!(a.try_add(&b), Ok(&a + &b));
+        assert_eq!(a.try_sub(&b), Ok(&a - &b));
+        assert_eq!(a.try_mul(&b), Ok(&a * &b));
+        assert_eq!(a.clone().try_set(&b), Ok(b.clone()));
+
+        assert_eq!(a.try_add(&c), Err(PbcError::FieldMismatch));
+        assert_eq!(a.try_sub(&c), Err(PbcError::FieldMismatch));
+        assert_eq!(a.try_mul(&c), Err(PbcError::FieldMismatch));
+        assert_eq!(a.try_div(&c), Err(PbcError::FieldMismatch));
+        assert_eq!(a.clone().try_set(&c), Err(PbcError::FieldMismatch));
+
+        let zero = ZrField::zero_element(field);
+        assert_eq!(a.try_div(&zero), Err(PbcError::NotInvertible));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_add_from_different_fields() {
+        let a = ZrField::random_element(field());
+        let b = ZrField::random_element(Arc::new(ZrField::new(Integer::from(44483))));
+        let _ = a + b;
+    }
+
+    #[test]
+    fn test_panic_message_hides_operands() {
+        let a = Zr::new(Integer::from(31337), field());
+        let b = Zr::new(Integer::from(4242), Arc::new(ZrField::new(Integer::from(44483))));
+        let error = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| &a * &b)).unwrap_err();
+        let message = error.downcast_ref::<String>().unwrap();
+        assert_eq!(message, &PbcError::FieldMismatch.to_string());
+    }
+
+    #[test]
+    fn test_constant_time_operations() {
+        let field = field();
+        let mut a = ZrField::random_element(Arc::clone(&field));
+        let mut b = &a + &ZrField::one_element(Arc::clone(&field));
+        let (a0, b0) = (a.clone(), b.clone());
+        assert!(bool::from(a.ct_eq(&a0)));
+        assert!(!bool::from(a.ct_eq(&b)));
+        Zr::conditional_swap(&mut a, &mut b, Choice::from(0));
+        assert_eq!((&a, &b), (&a0, &b0));
+        Zr::conditional_swap(&mut a, &mut b, Choice::from(1));
+        assert_eq!((&a, &b), (&b0, &a0));
+
+        let exponent = ZrField::random_element(Arc::clone(&field));
+        assert_eq!(a.pow_ct(&exponent.to_limbs()), a.pow(&exponent.value()));
+        assert_eq!(a.pow_ct(&[0, 0]), ZrField::one_element(Arc::clone(&field)));
+        assert_eq!(a.invert_ct().unwrap(), a.invert().unwrap());
+        assert!(bool::from(ZrField::zero_element(Arc::clone(&field)).invert_ct().is_none()));
+
+        for _ in 0..20 {
+            let a = ZrField::random_element(Arc::clone(&field));
+            assert_eq!(bool::from(a.is_sqrt_ct()), a.is_sqrt());
+            let root = Option::<Zr>::from(a.sqrt_ct());
+            assert_eq!(root.is_some(), a.is_sqrt());
+            if let Some(root) = root {
+                assert_eq!(root.square(), a);
+            }
+        }
+        let zero = ZrField::zero_element(field);
+        assert!(bool::from(zero.is_sqrt_ct()));
+        assert_eq!(zero.sqrt_ct().unwrap(), zero);
+    }
+
+    #[test]
+    fn test_debug() {
+        let a = Zr::new(Integer::from(31337), field());
+        assert!(format!("{:?}", a).starts_with("Zr { value: [REDACTED], field: "));
+        assert!(format!("{:#?}", a).contains("31337"));
+    }
+
+    #[test]
+    fn test_try_new() {
+        assert!(ZrField::try_new(Integer::from(ORDER)).is_ok());
+        assert_eq!(ZrField::try_new(Integer::from(44496)), Err(PbcError::InvalidOrder(Integer::from(44496))));
+        assert_eq!(ZrField::try_new(Integer::from(2)), Err(PbcError::InvalidOrder(Integer::from(2))));
+        assert_eq!(ZrField::try_with_nqr(Integer::from(ORDER), Integer::from(4)), Err(PbcError::InvalidNqr(Integer::from(4))));
+    }
+}
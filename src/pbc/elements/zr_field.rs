@@ -1,91 +1,147 @@
-use gmp::mpz::{Mpz,ProbabPrimeResult};
-use super::Zr;
-use std::rc::Rc;
-use gmp::rand::RandState;
+use crate::pbc::{BigInteger, Integer, ProbabPrimeResult};
+use super::{ByteLength, Zr};
+use super::montgomery::Montgomery;
+use std::sync::Arc;
 use rand::*;
+use sha2::{Digest, Sha256};
 use super::traits::*;
-use std::cell::RefCell;
+use crate::pbc::PbcError;
 
 #[derive(Debug, Clone)]
 pub struct ZrField {
-    order: Mpz,
-    nqr: Mpz
+    order: Integer,
+    nqr: Integer,
+    montgomery: Montgomery,
+    /// the Montgomery representation of `nqr`
+    nqr_montgomery: Vec<u64>,
 }
 
 impl Field<Zr, AtomicElement> for ZrField {
     
-    fn random_element(self: Rc<Self>) -> Zr {
-        let mut rng1 = rand::thread_rng();
-        let mut rng2 = RandState::new();
-        rng2.seed(Mpz::from(rng1.next_u64()));
-        Zr::new(rng2.urandom(self.order()), Rc::clone(&self))
+    fn random_element_with<R: RngCore + CryptoRng>(self: Arc<Self>, rng: &mut R) -> Zr {
+        Zr::new(Integer::urandom(rng, self.order()), Arc::clone(&self))
     }
 }
 
 impl HasOne<Zr> for ZrField {
-    fn one_element(self: Rc<Self>) -> Zr {
-        Zr::new(Mpz::from(1), Rc::clone(&self))
+    fn one_element(self: Arc<Self>) -> Zr {
+        Zr::from_montgomery(self.montgomery.one().to_vec(), self)
     }
 }
 
 impl HasZero<Zr> for ZrField {
-    fn zero_element(self: Rc<Self>) -> Zr {
-        Zr::new(Mpz::from(0), Rc::clone(&self))
+    fn zero_element(self: Arc<Self>) -> Zr {
+        Zr::from_montgomery(self.montgomery.zero(), self)
     }
 }
 
 impl FiniteField<Zr, AtomicElement> for ZrField {
-    fn order(&self) -> &Mpz { &self.order }
+    fn order(&self) -> &Integer { &self.order }
+}
+
+impl ByteLength for ZrField {
+    fn length_in_bytes(&self) -> usize {
+        self.order.bit_length().div_ceil(8)
+    }
 }
 
 impl ZrField {
-    pub fn new(order: Mpz) -> ZrField {
-        assert!(order.probab_prime(10) == ProbabPrimeResult::Prime);
+    pub fn new(order: Integer) -> ZrField {
+        Self::try_new(order).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// creates a field with a random quadratic nonresidue, or fails if
+    /// `order` is not an odd prime
+    pub fn try_new(order: Integer) -> Result<ZrField, PbcError> {
+        Self::check_order(&order)?;
 
         let nqr = {
-            let tmp_field = Rc::new(ZrField {
-                order: order.clone(),
-                nqr: Mpz::zero()
-            });
+            let tmp_field = Arc::new(ZrField::with_params(order.clone(), Integer::zero()));
 
             let nqr;
             loop {
-                let res = ZrField::random_element(Rc::clone(&tmp_field));
-                if ! (res.is_zero() || res.is_one()) {
-                    if ! res.is_sqrt() {
-                        nqr = res;
-                        break;
-                    }
+                let res = ZrField::random_element(Arc::clone(&tmp_field));
+                if ! (res.is_zero() || res.is_one() || res.is_sqrt()) {
+                    nqr = res;
+                    break;
                 }
             }
-            nqr.value().clone()
+            nqr.value()
         };
 
-        let field = ZrField {
-            order: order,
-            nqr: nqr
-        };
-        
-        field
+        Ok(ZrField::with_params(order, nqr))
+    }
+
+    /// creates a field with a fixed quadratic nonresidue, which is required
+    /// if quadratic extensions of two fields need to be compatible, e.g.
+    /// F_q[i] with i² = -1
+    pub fn with_nqr(order: Integer, nqr: Integer) -> ZrField {
+        Self::try_with_nqr(order, nqr).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_with_nqr(order: Integer, nqr: Integer) -> Result<ZrField, PbcError> {
+        Self::check_order(&order)?;
+
+        let field = ZrField::with_params(order.clone(), nqr.modulus(&order));
+        if field.nqr.is_zero() || field.legendre(&field.nqr) == Integer::one() {
+            return Err(PbcError::InvalidNqr(nqr));
+        }
+        Ok(field)
+    }
+
+    fn with_params(order: Integer, nqr: Integer) -> ZrField {
+        let montgomery = Montgomery::new(&order);
+        let nqr_montgomery = montgomery.encode(&nqr);
+        ZrField { order, nqr, montgomery, nqr_montgomery }
+    }
+
+    /// constants for the representation of the elements
+    pub(crate) fn montgomery(&self) -> &Montgomery { &self.montgomery }
+
+    fn check_order(order: &Integer) -> Result<(), PbcError> {
+        if order <= &Integer::from(2) || BigInteger::probab_prime(order, 10) == ProbabPrimeResult::NotPrime {
+            return Err(PbcError::InvalidOrder(order.clone()));
+        }
+        Ok(())
     }
 
-    pub fn two_inverse(&self) -> Mpz { self.inverse_of(&Mpz::from(2)) }
+    pub fn two_inverse(&self) -> Integer { self.inverse_of(&Integer::from(2)) }
 
-    pub fn inverse_of(&self, value: &Mpz) -> Mpz {
-        value.invert(self.order()).expect("unable to invert")
+    pub fn inverse_of(&self, value: &Integer) -> Integer {
+        BigInteger::invert(value, self.order()).expect("unable to invert")
     }
 
-    pub fn legendre(&self, value: &Mpz) -> Mpz {
-        let exp = (self.order()-Mpz::one()) / Mpz::from(2 as u32);
-        value.powm(&exp, self.order())
+    pub fn legendre(&self, value: &Integer) -> Integer {
+        let exp = (self.order()-Integer::one()) / Integer::from(2_u32);
+        BigInteger::powm(value, &exp, self.order())
     }
 
-    pub fn two(field: Rc<ZrField>) -> Zr { Zr::new(Mpz::from(2), Rc::clone(&field)) }
+    pub fn two(field: Arc<ZrField>) -> Zr { Zr::new(Integer::from(2), Arc::clone(&field)) }
+
+    /// maps arbitrary data to an element, like PBC's `element_from_hash`.
+    ///
+    /// The data is expanded with SHA-256 to 128 bits more than the size of
+    /// the order, so that the reduction modulo the order has a negligible
+    /// bias.
+    pub fn from_hash(self: Arc<Self>, data: &[u8]) -> Zr {
+        let length = self.length_in_bytes() + 16;
+        let mut bytes = Vec::with_capacity(length + 32);
+        let mut counter: u32 = 0;
+        while bytes.len() < length {
+            bytes.extend(Sha256::new()
+                .chain_update(counter.to_be_bytes())
+                .chain_update(data)
+                .finalize());
+            counter += 1;
+        }
+        bytes.truncate(length);
+        Zr::new(Integer::from(&bytes[..]), self)
+    }
 }
 
 impl HasNqr<Zr, AtomicElement> for ZrField {
-    fn nqr(field: Rc<Self>) -> Zr {
-        Zr::new(field.nqr.clone(), Rc::clone(&field))
+    fn nqr(field: Arc<Self>) -> Zr {
+        Zr::from_montgomery(field.nqr_montgomery.clone(), field)
     }
 }
 
@@ -93,4 +149,44 @@ impl PartialEq for ZrField {
     fn eq(&self, other: &Self) -> bool {
         self.order == other.order && self.nqr == other.nqr
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pbc::testlib::algebra::ORDER;
+
+    #[test]
+    fn test_from_hash() {
+        let field = Arc::new(ZrField::new(Integer::from(ORDER)));
+        let a = Arc::clone(&field).from_hash(b"alice@example.com");
+        assert_eq!(a, Arc::clone(&field).from_hash(b"alice@example.com"));
+        assert_ne!(a, Arc::clone(&field).from_hash(b"bob@example.com"));
+        assert!(&a.value() < field.order());
+
+        // the reduced values should cover the whole field
+        let mut seen = [false; 16];
+        for i in 0..1000_u32 {
+            let value = Arc::clone(&field).from_hash(&i.to_be_bytes());
+            seen[(value.value() * Integer::from(16) / field.order()).to_string().parse::<usize>().unwrap()] = true;
+        }
+        assert!(seen.iter().all(|s| *s));
+    }
+
+    #[test]
+    fn test_random_element_with() {
+        use rand::SeedableRng;
+        use rand::rngs::StdRng;
+
+        // an order above 2⁶⁴ must be covered completely
+        let mut order = Integer::zero();
+        order.setbit(127);
+        let field = Arc::new(ZrField::new(order - Integer::one()));
+        let a = Arc::clone(&field).random_element_with(&mut StdRng::seed_from_u64(1));
+        let b = Arc::clone(&field).random_element_with(&mut StdRng::seed_from_u64(1));
+        assert_eq!(a, b);
+
+        let mut rng = StdRng::seed_from_u64(2);
+        assert!((0..10).any(|_| Arc::clone(&field).random_element_with(&mut rng).value().bit_length() > 64));
+    }
+}
@@ -0,0 +1,58 @@
+use crate::pbc::Integer;
+use std::fmt;
+use super::{BytesError, CurveType, ParamError};
+
+/// errors of fallible operations, like `Element::try_add` or
+/// `ZrField::try_new`
+#[derive(Debug, Clone, PartialEq)]
+pub enum PbcError {
+    /// the operands of an operation belong to different fields
+    FieldMismatch,
+    /// the divisor is not invertible, e.g. it is zero
+    NotInvertible,
+    /// the order of a prime field must be an odd prime
+    InvalidOrder(Integer),
+    /// the value is a quadratic residue (or zero) in the field
+    InvalidNqr(Integer),
+    /// there is no pairing on curves of this type, e.g. `Pairing::try_new`
+    /// only supports Type A curves
+    UnsupportedCurve(CurveType),
+    Bytes(BytesError),
+    Param(ParamError),
+}
+
+impl fmt::Display for PbcError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PbcError::FieldMismatch => write!(f, "the elements belong to different fields"),
+            PbcError::NotInvertible => write!(f, "the element is not invertible"),
+            PbcError::InvalidOrder(order) => write!(f, "{} is not an odd prime", order),
+            PbcError::InvalidNqr(nqr) => write!(f, "{} is not a quadratic nonresidue", nqr),
+            PbcError::UnsupportedCurve(curve_type) => write!(f, "pairings on curves of type '{}' are not supported", curve_type),
+            PbcError::Bytes(error) => write!(f, "{}", error),
+            PbcError::Param(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for PbcError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PbcError::Bytes(error) => Some(error),
+            PbcError::Param(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<BytesError> for PbcError {
+    fn from(error: BytesError) -> Self {
+        PbcError::Bytes(error)
+    }
+}
+
+impl From<ParamError> for PbcError {
+    fn from(error: ParamError) -> Self {
+        PbcError::Param(error)
+    }
+}
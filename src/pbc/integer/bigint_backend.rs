@@ -0,0 +1,310 @@
+use num_bigint::{BigInt, BigUint, Sign};
+use num_integer::Integer as _;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+use std::fmt;
+use std::ops::*;
+use std::str::FromStr;
+use super::BigInteger;
+
+/// the result of a primality test, like the one of GMP
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProbabPrimeResult {
+    NotPrime,
+    ProbablyPrime,
+    Prime,
+}
+
+/// the error which is returned if a string cannot be parsed as integer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseIntegerError;
+
+impl fmt::Display for ParseIntegerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid integer")
+    }
+}
+
+impl std::error::Error for ParseIntegerError {}
+
+/// a big integer with the interface of `gmp::mpz::Mpz`, so that the rest
+/// of the crate does not depend on the backend
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Integer(BigInt);
+
+/// primes below 256, which are used for trial division
+const SMALL_PRIMES: [u32; 54] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71,
+    73, 79, 83, 89, 97, 101, 103, 107, 109, 113, 127, 131, 137, 139, 149, 151,
+    157, 163, 167, 173, 179, 181, 191, 193, 197, 199, 211, 223, 227, 229, 233,
+    239, 241, 251,
+];
+
+impl Integer {
+    pub fn new() -> Integer { Integer(BigInt::zero()) }
+    pub fn zero() -> Integer { Integer(BigInt::zero()) }
+    pub fn one() -> Integer { Integer(BigInt::one()) }
+    pub fn is_zero(&self) -> bool { self.0.is_zero() }
+
+    pub fn from_str_radix(s: &str, base: u8) -> Result<Integer, ParseIntegerError> {
+        BigInt::parse_bytes(s.as_bytes(), base as u32).map(Integer).ok_or(ParseIntegerError)
+    }
+
+    pub fn to_str_radix(&self, base: u8) -> String { self.0.to_str_radix(base as u32) }
+
+    pub fn bit_length(&self) -> usize { (self.0.bits() as usize).max(1) }
+
+    pub fn abs(&self) -> Integer { Integer(self.0.abs()) }
+
+    pub fn sqrt(&self) -> Integer { Integer(self.0.sqrt()) }
+
+    pub fn pow(&self, exp: u32) -> Integer { Integer(self.0.pow(exp)) }
+
+    pub fn gcd(&self, other: &Integer) -> Integer { Integer(self.0.gcd(&other.0)) }
+
+    pub fn is_multiple_of(&self, other: &Integer) -> bool { self.0.is_multiple_of(&other.0) }
+
+    pub fn div_floor(&self, other: &Integer) -> Integer { Integer(self.0.div_floor(&other.0)) }
+
+    pub fn mod_floor(&self, other: &Integer) -> Integer { Integer(self.0.mod_floor(&other.0)) }
+
+    /// the remainder in [0, |modulo|)
+    pub fn modulus(&self, modulo: &Integer) -> Integer {
+        if modulo.is_zero() {
+            panic!("divide by zero")
+        }
+        Integer(self.0.mod_floor(&modulo.0.abs()))
+    }
+
+    pub fn invert(&self, modulo: &Integer) -> Option<Integer> {
+        let modulo = modulo.0.abs();
+        if modulo.is_one() {
+            return Some(Integer::zero());
+        }
+        self.0.modinv(&modulo).map(Integer)
+    }
+
+    pub fn powm(&self, exp: &Integer, modulus: &Integer) -> Integer {
+        let modulus = modulus.0.abs();
+        if exp.0.is_negative() {
+            let inverse = self.invert(&Integer(modulus.clone())).expect("the base is not invertible");
+            Integer(inverse.0.modpow(&-&exp.0, &modulus))
+        } else {
+            Integer(self.0.modpow(&exp.0, &modulus))
+        }
+    }
+
+    pub fn probab_prime(&self, reps: i32) -> ProbabPrimeResult {
+        let n = self.0.abs();
+        if n < BigInt::from(2) {
+            return ProbabPrimeResult::NotPrime;
+        }
+        for p in SMALL_PRIMES {
+            let p = BigInt::from(p);
+            if n == p {
+                return ProbabPrimeResult::Prime;
+            }
+            if n.is_multiple_of(&p) {
+                return ProbabPrimeResult::NotPrime;
+            }
+        }
+        // there are no composite numbers without prime factors below 256
+        if n < BigInt::from(256 * 256) {
+            return ProbabPrimeResult::Prime;
+        }
+
+        // Miller-Rabin with `reps` random bases in [2, n-2]
+        let n_minus_one = &n - 1_u32;
+        let s = n_minus_one.trailing_zeros().unwrap_or(0);
+        let d = &n_minus_one >> s;
+        let bases = Integer(&n - 3_u32);
+        let mut rng = rand::thread_rng();
+        for _ in 0..reps.max(1) {
+            let a = Integer::urandom(&mut rng, &bases).0 + 2_u32;
+            let mut x = a.modpow(&d, &n);
+            if x.is_one() || x == n_minus_one {
+                continue;
+            }
+            let mut witness = true;
+            for _ in 1..s {
+                x = (&x * &x).mod_floor(&n);
+                if x == n_minus_one {
+                    witness = false;
+                    break;
+                }
+            }
+            if witness {
+                return ProbabPrimeResult::NotPrime;
+            }
+        }
+        ProbabPrimeResult::ProbablyPrime
+    }
+
+    pub fn nextprime(&self) -> Integer {
+        if self.0 < BigInt::from(2) {
+            return Integer::from(2);
+        }
+        let mut candidate = Integer(&self.0 + 1_u32);
+        if candidate.0.is_even() && candidate.0 != BigInt::from(2) {
+            candidate.0 += 1_u32;
+        }
+        while candidate.probab_prime(25) == ProbabPrimeResult::NotPrime {
+            candidate.0 += 2_u32;
+        }
+        candidate
+    }
+
+    pub fn setbit(&mut self, bit_index: usize) { self.0.set_bit(bit_index as u64, true) }
+
+    pub fn clrbit(&mut self, bit_index: usize) { self.0.set_bit(bit_index as u64, false) }
+
+    /// uses the two's complement for negative numbers, like GMP
+    pub fn tstbit(&self, bit_index: usize) -> bool { self.0.bit(bit_index as u64) }
+}
+
+/// overwrites the digits with zeros, so that the value does not remain in
+/// memory after it has been freed. The bits are cleared from the lowest
+/// one, because `num-bigint` might reallocate once the highest digit is
+/// zero. Spare capacity of the digits cannot be reached.
+#[cfg(feature = "zeroize")]
+pub(crate) fn wipe(value: &mut Integer) {
+    // negating an owned value keeps its digits
+    let mut magnitude = std::mem::take(&mut value.0);
+    if magnitude.is_negative() {
+        magnitude = -magnitude;
+    }
+    for bit in 0..magnitude.bits() {
+        magnitude.set_bit(bit, false);
+    }
+    value.0 = magnitude;
+}
+
+impl BigInteger for Integer {
+    fn powm(&self, exp: &Self, modulus: &Self) -> Self { Integer::powm(self, exp, modulus) }
+    fn invert(&self, modulus: &Self) -> Option<Self> { Integer::invert(self, modulus) }
+    fn probab_prime(&self, reps: i32) -> ProbabPrimeResult { Integer::probab_prime(self, reps) }
+    fn bit_length(&self) -> usize { Integer::bit_length(self) }
+    fn to_u64_digits(&self) -> Vec<u64> { self.0.magnitude().to_u64_digits() }
+
+    fn from_u64_digits(digits: &[u64]) -> Self {
+        let digits = digits.iter().flat_map(|digit| [*digit as u32, (*digit >> 32) as u32]).collect();
+        Integer(BigInt::from_biguint(Sign::Plus, BigUint::new(digits)))
+    }
+}
+
+impl Zero for Integer {
+    fn zero() -> Integer { Integer::zero() }
+    fn is_zero(&self) -> bool { Integer::is_zero(self) }
+}
+
+impl One for Integer {
+    fn one() -> Integer { Integer::one() }
+}
+
+impl fmt::Display for Integer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Debug for Integer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl FromStr for Integer {
+    type Err = ParseIntegerError;
+    fn from_str(s: &str) -> Result<Integer, ParseIntegerError> { Integer::from_str_radix(s, 10) }
+}
+
+/// interprets `bytes` as unsigned big endian number
+impl<'a> From<&'a [u8]> for Integer {
+    fn from(bytes: &'a [u8]) -> Integer { Integer(BigInt::from_bytes_be(Sign::Plus, bytes)) }
+}
+
+/// the big endian bytes of the absolute value
+impl<'a> From<&'a Integer> for Vec<u8> {
+    fn from(value: &'a Integer) -> Vec<u8> { value.0.to_bytes_be().1 }
+}
+
+impl<'a> From<&'a Integer> for Option<u64> {
+    fn from(value: &'a Integer) -> Option<u64> { value.0.to_u64() }
+}
+
+impl<'a> From<&'a Integer> for Option<i64> {
+    fn from(value: &'a Integer) -> Option<i64> { value.0.to_i64() }
+}
+
+macro_rules! impl_from {
+    ($($t:ty)*) => {
+        $(impl From<$t> for Integer { fn from(value: $t) -> Integer { Integer(BigInt::from(value)) } })*
+    };
+}
+impl_from!(i32 i64 u32 u64);
+
+macro_rules! impl_binary_operator {
+    ($tr:ident, $method:ident, $tr_assign:ident, $method_assign:ident) => {
+        impl $tr<Integer> for Integer {
+            type Output = Integer;
+            fn $method(self, rhs: Integer) -> Integer { Integer($tr::$method(self.0, rhs.0)) }
+        }
+        impl<'a> $tr<&'a Integer> for Integer {
+            type Output = Integer;
+            fn $method(self, rhs: &'a Integer) -> Integer { Integer($tr::$method(self.0, &rhs.0)) }
+        }
+        impl<'a> $tr<Integer> for &'a Integer {
+            type Output = Integer;
+            fn $method(self, rhs: Integer) -> Integer { Integer($tr::$method(&self.0, rhs.0)) }
+        }
+        impl<'a, 'b> $tr<&'b Integer> for &'a Integer {
+            type Output = Integer;
+            fn $method(self, rhs: &'b Integer) -> Integer { Integer($tr::$method(&self.0, &rhs.0)) }
+        }
+        impl $tr_assign<Integer> for Integer {
+            fn $method_assign(&mut self, rhs: Integer) { $tr_assign::$method_assign(&mut self.0, rhs.0) }
+        }
+        impl<'a> $tr_assign<&'a Integer> for Integer {
+            fn $method_assign(&mut self, rhs: &'a Integer) { $tr_assign::$method_assign(&mut self.0, &rhs.0) }
+        }
+    };
+    ($t:ty, $tr:ident, $method:ident, $tr_assign:ident, $method_assign:ident) => {
+        impl $tr<$t> for Integer {
+            type Output = Integer;
+            fn $method(self, rhs: $t) -> Integer { Integer($tr::$method(self.0, rhs)) }
+        }
+        impl<'a> $tr<$t> for &'a Integer {
+            type Output = Integer;
+            fn $method(self, rhs: $t) -> Integer { Integer($tr::$method(&self.0, rhs)) }
+        }
+        impl $tr_assign<$t> for Integer {
+            fn $method_assign(&mut self, rhs: $t) { $tr_assign::$method_assign(&mut self.0, rhs) }
+        }
+    };
+}
+
+impl_binary_operator!(Add, add, AddAssign, add_assign);
+impl_binary_operator!(Sub, sub, SubAssign, sub_assign);
+impl_binary_operator!(Mul, mul, MulAssign, mul_assign);
+impl_binary_operator!(Div, div, DivAssign, div_assign);
+impl_binary_operator!(Rem, rem, RemAssign, rem_assign);
+impl_binary_operator!(BitAnd, bitand, BitAndAssign, bitand_assign);
+impl_binary_operator!(BitOr, bitor, BitOrAssign, bitor_assign);
+impl_binary_operator!(BitXor, bitxor, BitXorAssign, bitxor_assign);
+impl_binary_operator!(u64, Add, add, AddAssign, add_assign);
+impl_binary_operator!(u64, Sub, sub, SubAssign, sub_assign);
+impl_binary_operator!(u64, Mul, mul, MulAssign, mul_assign);
+impl_binary_operator!(u64, Div, div, DivAssign, div_assign);
+impl_binary_operator!(u64, Rem, rem, RemAssign, rem_assign);
+impl_binary_operator!(i64, Mul, mul, MulAssign, mul_assign);
+impl_binary_operator!(usize, Shl, shl, ShlAssign, shl_assign);
+impl_binary_operator!(usize, Shr, shr, ShrAssign, shr_assign);
+
+impl Neg for Integer {
+    type Output = Integer;
+    fn neg(self) -> Integer { Integer(-self.0) }
+}
+
+impl Neg for &Integer {
+    type Output = Integer;
+    fn neg(self) -> Integer { Integer(-&self.0) }
+}
@@ -0,0 +1,62 @@
+pub use gmp::mpz::{Mpz as Integer, ProbabPrimeResult};
+use gmp::mpz::{mpz_ptr, mpz_srcptr};
+use std::os::raw::{c_int, c_void};
+use super::BigInteger;
+
+impl BigInteger for Integer {
+    fn powm(&self, exp: &Self, modulus: &Self) -> Self { Integer::powm(self, exp, modulus) }
+    fn invert(&self, modulus: &Self) -> Option<Self> { Integer::invert(self, modulus) }
+    fn probab_prime(&self, reps: i32) -> ProbabPrimeResult { Integer::probab_prime(self, reps) }
+    fn bit_length(&self) -> usize { Integer::bit_length(self) }
+
+    fn to_u64_digits(&self) -> Vec<u64> {
+        let mut digits = vec![0; self.bit_length().div_ceil(64)];
+        let mut count = 0;
+        // SAFETY: `digits` has room for all words of the value
+        unsafe {
+            __gmpz_export(digits.as_mut_ptr() as *mut c_void, &mut count, -1, 8, 0, 0, self.inner());
+        }
+        digits.truncate(count);
+        digits
+    }
+
+    fn from_u64_digits(digits: &[u64]) -> Self {
+        let mut value = Integer::new();
+        // SAFETY: `digits` contains `digits.len()` words
+        unsafe {
+            __gmpz_import(value.inner_mut(), digits.len(), -1, 8, 0, 0, digits.as_ptr() as *const c_void);
+        }
+        value
+    }
+}
+
+// rust-gmp only uses these functions for conversions from and to bytes
+extern "C" {
+    fn __gmpz_export(rop: *mut c_void, countp: *mut usize, order: c_int, size: usize, endian: c_int, nails: usize, op: mpz_srcptr) -> *mut c_void;
+    fn __gmpz_import(rop: mpz_ptr, count: usize, order: c_int, size: usize, endian: c_int, nails: usize, op: *const c_void);
+}
+
+/// overwrites all allocated limbs with zeros, so that the value does not
+/// remain in memory after it has been freed
+#[cfg(feature = "zeroize")]
+pub(crate) fn wipe(value: &mut Integer) {
+    use zeroize::Zeroize;
+
+    /// the layout of `mpz_t`, whose fields are not exported by rust-gmp
+    #[repr(C)]
+    struct RawMpz {
+        alloc: c_int,
+        size: c_int,
+        limbs: *mut usize,
+    }
+
+    // SAFETY: `inner_mut` points to the `mpz_t` of `value`, whose `limbs`
+    // has `alloc` allocated limbs
+    unsafe {
+        let raw = &mut *(value.inner_mut() as *mut RawMpz);
+        if raw.alloc > 0 {
+            std::slice::from_raw_parts_mut(raw.limbs, raw.alloc as usize).zeroize();
+        }
+        raw.size = 0;
+    }
+}
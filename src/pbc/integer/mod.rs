@@ -0,0 +1,158 @@
+//! the big integer type `Integer`, which is a type alias selected at
+//! compile time: GMP with the feature `backend-gmp` (the default) or a pure
+//! Rust implementation based on `num-bigint` with the feature
+//! `backend-num-bigint`. Exactly one of them has to be enabled, so the
+//! default features have to be disabled for the latter:
+//!
+//! ```text
+//! cargo build --no-default-features --features backend-num-bigint
+//! ```
+//!
+//! Both backends provide the inherent methods of `Integer` with the
+//! semantics of GMP. The crate calls the number theoretic ones through
+//! `BigInteger`, so that both backends have to implement them.
+
+#[cfg(feature = "backend-gmp")]
+mod gmp_backend;
+#[cfg(feature = "backend-gmp")]
+pub use gmp_backend::*;
+
+// both features are rejected in the crate root, which should be the only
+// error then
+#[cfg(all(feature = "backend-num-bigint", not(feature = "backend-gmp")))]
+mod bigint_backend;
+#[cfg(all(feature = "backend-num-bigint", not(feature = "backend-gmp")))]
+pub use bigint_backend::*;
+
+use num_traits::Zero;
+use rand::RngCore;
+
+/// the operations which the backends implement on top of their types, and
+/// which the crate calls through this trait
+pub(crate) trait BigInteger: Sized + Ord + Zero + for<'a> From<&'a [u8]> {
+    /// computes `self^exp mod modulus`, negative exponents use the inverse
+    fn powm(&self, exp: &Self, modulus: &Self) -> Self;
+
+    /// returns the inverse in [0, modulus), if there is one
+    fn invert(&self, modulus: &Self) -> Option<Self>;
+
+    /// runs some trial divisions and `reps` rounds of Miller-Rabin
+    fn probab_prime(&self, reps: i32) -> ProbabPrimeResult;
+
+    /// the number of bits of the absolute value, which is 1 for zero
+    fn bit_length(&self) -> usize;
+
+    /// the little-endian 64 bit words of the absolute value, without
+    /// leading zero words
+    fn to_u64_digits(&self) -> Vec<u64>;
+
+    /// the non-negative value of little-endian 64 bit words
+    fn from_u64_digits(digits: &[u64]) -> Self;
+
+    /// returns a uniformly distributed integer in [0, max) by rejection
+    /// sampling, i.e. random numbers with the bit length of `max` are drawn
+    /// until one of them is less than `max`
+    fn urandom<R: RngCore + ?Sized>(rng: &mut R, max: &Self) -> Self {
+        assert!(max > &Self::zero());
+        let bits = max.bit_length();
+        let mut bytes = vec![0; bits.div_ceil(8)];
+        let mask = 0xff_u8 >> (bytes.len() * 8 - bits);
+        loop {
+            rng.fill_bytes(&mut bytes);
+            bytes[0] &= mask;
+            let value = Self::from(&bytes[..]);
+            if &value < max {
+                return value;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn test_urandom() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let max = Integer::from(5);
+        let mut seen = [false; 5];
+        for _ in 0..100 {
+            let value = Integer::urandom(&mut rng, &max);
+            assert!(value < max);
+            seen[value.to_string().parse::<usize>().unwrap()] = true;
+        }
+        assert!(seen.iter().all(|s| *s));
+
+        let mut max = Integer::zero();
+        max.setbit(256);
+        for _ in 0..100 {
+            assert!(Integer::urandom(&mut rng, &max) < max);
+        }
+    }
+
+    #[test]
+    fn test_powm_and_invert() {
+        let modulus = Integer::from(44497);
+        let a = Integer::from(12345);
+        let inverse = BigInteger::invert(&a, &modulus).unwrap();
+        assert_eq!((&a * &inverse).modulus(&modulus), Integer::from(1));
+        assert_eq!(BigInteger::powm(&a, &Integer::from(-1), &modulus), inverse);
+        assert_eq!(BigInteger::powm(&a, &Integer::from(44496), &modulus), Integer::from(1));
+        assert_eq!(BigInteger::powm(&Integer::from(-2), &Integer::from(3), &modulus), Integer::from(44489));
+        assert_eq!(BigInteger::invert(&Integer::from(0), &modulus), None);
+        assert_eq!(BigInteger::invert(&Integer::from(6), &Integer::from(9)), None);
+    }
+
+    #[test]
+    fn test_probab_prime() {
+        for p in [2_u64, 3, 5, 44497, 65537, 2_147_483_647, 2_305_843_009_213_693_951] {
+            assert!(BigInteger::probab_prime(&Integer::from(p), 20) != ProbabPrimeResult::NotPrime, "{}", p);
+        }
+        let mut mersenne = Integer::zero();
+        mersenne.setbit(127);
+        assert!(BigInteger::probab_prime(&(&mersenne - Integer::from(1)), 20) != ProbabPrimeResult::NotPrime);
+        assert!(BigInteger::probab_prime(&(&mersenne + Integer::from(1)), 20) == ProbabPrimeResult::NotPrime);
+        // 561 is a Carmichael number
+        for n in [0_u64, 1, 4, 561, 44496, 65535, 4_294_967_297] {
+            assert!(BigInteger::probab_prime(&Integer::from(n), 20) == ProbabPrimeResult::NotPrime, "{}", n);
+        }
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn test_wipe() {
+        let mut value = Integer::zero();
+        value.setbit(300);
+        value = -(value - Integer::from(12345));
+        wipe(&mut value);
+        assert!(value.is_zero());
+
+        let mut zero = Integer::zero();
+        wipe(&mut zero);
+        assert!(zero.is_zero());
+    }
+
+    #[test]
+    fn test_u64_digits() {
+        let mut value = Integer::zero();
+        value.setbit(130);
+        let value = value + Integer::from(u64::MAX);
+        assert_eq!(value.to_u64_digits(), vec![u64::MAX, 0, 4]);
+        assert_eq!((-&value).to_u64_digits(), vec![u64::MAX, 0, 4]);
+        assert_eq!(Integer::from_u64_digits(&[u64::MAX, 0, 4]), value);
+        assert_eq!(Integer::from_u64_digits(&[u64::MAX, 0, 4, 0]), value);
+        assert_eq!(Integer::zero().to_u64_digits(), Vec::<u64>::new());
+        assert_eq!(Integer::from_u64_digits(&[]), Integer::zero());
+    }
+
+    #[test]
+    fn test_bit_length() {
+        assert_eq!(BigInteger::bit_length(&Integer::from(0)), 1);
+        assert_eq!(BigInteger::bit_length(&Integer::from(1)), 1);
+        assert_eq!(BigInteger::bit_length(&Integer::from(-256)), 9);
+        assert_eq!(BigInteger::bit_length(&Integer::from(u64::MAX)), 64);
+    }
+}
@@ -1,14 +1,15 @@
-mod element;
 mod elements;
-mod TypaACurveGenerator;
+mod curve;
+mod pairing;
+mod error;
+mod integer;
 
 #[cfg(test)]
 #[macro_use] mod testlib;
 
-pub use element::*;
 pub use elements::*;
 pub use elements::traits::*;
-pub use TypaACurveGenerator::*;
-
-#[cfg(test)]
-pub use testlib::*;
+pub use curve::*;
+pub use pairing::*;
+pub use error::*;
+pub use integer::*;
@@ -0,0 +1,307 @@
+use crate::pbc::{BigInteger, Integer, PbcError};
+use crate::pbc::elements::traits::*;
+use crate::pbc::elements::{Quadratic, QuadraticField, Wiped, Zr, ZrField};
+use crate::pbc::elements::{BytesError, ByteLength, ToBytes, FromBytes};
+use rand::{CryptoRng, RngCore};
+use std::ops::{self, Neg};
+use std::sync::Arc;
+use subtle::{Choice, ConstantTimeEq};
+
+/// subgroup of order r of the multiplicative group of F_q², which is the
+/// target group of a Type A pairing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GTField {
+    target_field: Arc<QuadraticField<Zr, ZrField>>,
+    order: Integer,
+    cofactor: Integer,
+}
+
+/// element of the target group of a pairing.
+///
+/// As in PBC, the group operation is written multiplicatively as well as
+/// additively, i.e. `a * b` and `a + b` both denote multiplication in F_q².
+#[derive(Debug, Clone, PartialEq)]
+pub struct GT {
+    value: Quadratic<Zr, ZrField>,
+    field: Arc<GTField>,
+}
+
+impl GTField {
+    /// `cofactor` is (q + 1) / r, with F_q being the base field of
+    /// `target_field`
+    pub fn new(target_field: Arc<QuadraticField<Zr, ZrField>>, order: Integer, cofactor: Integer) -> GTField {
+        GTField {
+            target_field,
+            order,
+            cofactor,
+        }
+    }
+
+    pub fn target_field(&self) -> Arc<QuadraticField<Zr, ZrField>> {
+        Arc::clone(&self.target_field)
+    }
+
+    /// maps f to f^((q² - 1) / r), which is an element of GT.
+    ///
+    /// (q² - 1) / r = (q - 1)·(q + 1) / r, and f^q is the conjugate of f
+    /// in F_q[i], so f^(q - 1) can be calculated as conjugate(f) / f. The
+    /// remaining exponent is the cofactor.
+    pub fn final_pow(self: Arc<Self>, f: &Quadratic<Zr, ZrField>) -> GT {
+        let t = f.conjugate() / f;
+        let cofactor = self.cofactor.clone();
+        GT::new(t, self).pow(&cofactor)
+    }
+}
+
+impl HasZero<GT> for GTField {
+    fn zero_element(self: Arc<Self>) -> GT {
+        self.one_element()
+    }
+}
+
+impl HasOne<GT> for GTField {
+    fn one_element(self: Arc<Self>) -> GT {
+        GT::new(self.target_field().one_element(), self)
+    }
+}
+
+impl Field<GT, ComplexElement> for GTField {
+    fn random_element_with<R: RngCore + CryptoRng>(self: Arc<Self>, rng: &mut R) -> GT {
+        loop {
+            let f = self.target_field().random_element_with(rng);
+            if ! f.is_zero() {
+                let result = Arc::clone(&self).final_pow(&f);
+                if ! result.is_one() {
+                    return result;
+                }
+            }
+        }
+    }
+}
+
+impl FiniteField<GT, ComplexElement> for GTField {
+    fn order(&self) -> &Integer { &self.order }
+}
+
+impl ByteLength for GTField {
+    fn length_in_bytes(&self) -> usize {
+        self.target_field.length_in_bytes()
+    }
+}
+
+impl GT {
+    /// creates a new element, which is not checked to be of order r
+    pub fn new(value: Quadratic<Zr, ZrField>, field: Arc<GTField>) -> GT {
+        GT { value, field }
+    }
+
+    pub fn value(&self) -> &Quadratic<Zr, ZrField> { &self.value }
+
+    /// calculates `self^exp` with a Montgomery ladder, which performs the
+    /// same multiplications for all exponents of the field of `exp`, so that
+    /// a secret exponent does not influence the timing
+    pub fn pow_ct(&self, exp: &Zr) -> GT {
+        let exp = Wiped(exp.to_limbs());
+        // invariant: r1 = r0·self
+        let mut r0 = GTField::one_element(Arc::clone(&self.field));
+        let mut r1 = self.clone();
+        for i in (0..64 * exp.len()).rev() {
+            let bit = Choice::from(((exp[i / 64] >> (i % 64)) & 1) as u8);
+            GT::conditional_swap(&mut r0, &mut r1, bit);
+            r1 = &r0 * &r1;
+            r0 = r0.square();
+            GT::conditional_swap(&mut r0, &mut r1, bit);
+        }
+        r0
+    }
+
+    /// elements of GT have norm one, so their inverse is their conjugate
+    pub fn inverse(&self) -> GT {
+        GT::new(self.value.conjugate(), Arc::clone(&self.field))
+    }
+}
+
+impl Element<ComplexElement> for GT {
+    type FieldType = GTField;
+
+    fn field(&self) -> Arc<Self::FieldType> {
+        Arc::clone(&self.field)
+    }
+
+    fn invert(&self) -> Option<Self> { Some(self.inverse()) }
+    fn square(&self) -> Self { self * self }
+    fn double(&self) -> Self { self * self }
+
+    fn halve(&self) -> Self {
+        let two_inverse = BigInteger::invert(&Integer::from(2), self.field.order()).expect("unable to invert");
+        self.pow(&two_inverse)
+    }
+
+    /// every element of a group of odd order is a square
+    fn is_sqrt(&self) -> bool { true }
+
+    fn sqrt(&self) -> Option<(Self, Self)> {
+        let root = self.halve();
+        Some((root.clone(), root))
+    }
+
+    fn try_mul(&self, other: &Self) -> Result<Self, PbcError> {
+        check_same_field(self, other)?;
+        Ok(GT::new(&self.value * &other.value, Arc::clone(&self.field)))
+    }
+
+    fn try_add(&self, other: &Self) -> Result<Self, PbcError> { self.try_mul(other) }
+    fn try_sub(&self, other: &Self) -> Result<Self, PbcError> { self.try_mul(&other.inverse()) }
+
+    /// the inverse of an element always exists
+    fn try_div(&self, other: &Self) -> Result<Self, PbcError> { self.try_mul(&other.inverse()) }
+
+    fn try_set(mut self, other: &Self) -> Result<Self, PbcError> {
+        check_same_field(&self, other)?;
+        self.value = other.value.clone();
+        Ok(self)
+    }
+}
+
+impl ToBytes for GT {
+    fn length_in_bytes(&self) -> usize { self.value.length_in_bytes() }
+    fn to_bytes(&self) -> Vec<u8> { self.value.to_bytes() }
+}
+
+/// the value is not checked to be of order r
+impl FromBytes<GTField> for GT {
+    fn from_bytes(field: Arc<GTField>, bytes: &[u8]) -> Result<Self, BytesError> {
+        let value = Quadratic::from_bytes(field.target_field(), bytes)?;
+        Ok(GT::new(value, field))
+    }
+}
+
+impl CanBeZero for GT {
+    fn is_zero(&self) -> bool { self.value.is_one() }
+}
+
+impl CanBeOne for GT {
+    fn is_one(&self) -> bool { self.value.is_one() }
+}
+
+impl Set<&GT> for GT {
+    fn set(self, other: &Self) -> Self {
+        self.try_set(other).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl Neg for GT {
+    type Output = GT;
+    fn neg(self) -> Self::Output { self.inverse() }
+}
+
+/// both elements have to belong to the same group
+impl ConditionallyAssignable for GT {
+    fn conditional_assign(&mut self, other: &Self, choice: Choice) {
+        if check_same_field(self, other).is_err() {
+            panic!("{}", PbcError::FieldMismatch);
+        }
+        self.value.conditional_assign(&other.value, choice);
+    }
+}
+
+impl ConstantTimeEq for GT {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        Choice::from(check_same_field(self, other).is_ok() as u8) & self.value.ct_eq(&other.value)
+    }
+}
+
+macro_rules! add_operators {
+    ($($op:tt $method:ident),+) => {
+        $(
+            impl_op_ex!($op |lhs: &GT, rhs: &GT| -> GT {
+                lhs.$method(rhs).unwrap_or_else(|e| panic!("{}", e))
+            });
+        )+
+    };
+}
+add_operators!(* try_mul, + try_add, - try_sub, / try_div);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ops::*;
+    use crate::pbc::Pairing;
+    use crate::pbc::testlib::algebra::*;
+    use crate::pbc::testlib::curve::type_a_options;
+    use crate::test_one;
+    use crate::test_zero;
+    use crate::test_neg;
+    use crate::test_associativity;
+    use crate::test_commutativity;
+    use crate::test_double_and_halve;
+    use crate::test_pow;
+    use crate::test_invert;
+    use crate::test_bytes;
+    use crate::test_pow_preprocessed;
+    use crate::test_multi_exp;
+
+    fn field() -> Arc<GTField> { Pairing::new(&type_a_options()).gt() }
+
+    test_one!(GT, GTField, field());
+    test_zero!(GT, GTField, field());
+    test_neg!(GT, GTField, field());
+    test_double_and_halve!(GT, GTField, field());
+    test_commutativity!(GT, mul, GTField, field());
+    test_associativity!(GT, mul, GTField, field());
+    test_pow!(GT, GTField, field());
+    test_invert!(GT, GTField, field());
+    test_bytes!(GT, GTField, field());
+    test_pow_preprocessed!(GT, GTField, field());
+    test_multi_exp!(GT, GTField, field());
+
+    #[test]
+    fn test_order() {
+        let field = field();
+        let a = GTField::random_element(Arc::clone(&field));
+        assert!(a.pow(field.order()).is_one());
+        assert!((&a * &a.inverse()).is_one());
+    }
+
+    #[test]
+    fn test_try_operations() {
+        let field = field();
+        let other_field = Pairing::new(&crate::pbc::TypeACurveGenerator::new(24, 64).generate()).gt();
+        let a = GTField::random_element(Arc::clone(&field));
+        let b = GTField::random_element(field);
+        let c = GTField::random_element(other_field);
+
+        assert_eq!(a.try_mul(&b), Ok(&a * &b));
+        assert_eq!(a.try_add(&b), Ok(&a + &b));
+        assert_eq!(a.try_sub(&b), Ok(&a - &b));
+        assert_eq!(a.try_div(&b), Ok(&a / &b));
+        assert_eq!(a.clone().try_set(&b), Ok(b.clone()));
+        assert_eq!(a.try_mul(&c), Err(PbcError::FieldMismatch));
+        assert_eq!(a.try_div(&c), Err(PbcError::FieldMismatch));
+        assert_eq!(a.try_set(&c), Err(PbcError::FieldMismatch));
+    }
+
+    #[test]
+    fn test_pow_ct() {
+        let pairing = Pairing::new(&type_a_options());
+        let a = GTField::random_element(pairing.gt());
+        let n = pairing.zr().random_element();
+        assert_eq!(a.pow_ct(&n), a.pow_zn(&n));
+        assert!(a.pow_ct(&pairing.zr().zero_element()).is_one());
+        assert!(bool::from(a.ct_eq(&a.clone())));
+        assert!(!bool::from(a.ct_eq(&a.square())));
+    }
+
+    #[test]
+    fn test_bilinearity() {
+        let pairing = Pairing::new(&type_a_options());
+        let p = pairing.g1().random_element();
+        let q = pairing.g1().random_element();
+        let a = pairing.zr().random_element();
+        let b = pairing.zr().random_element();
+
+        let e1 = pairing.pair(&p.mul_zn(&a), &q.mul_zn(&b));
+        let e2 = pairing.pair(&p, &q).pow_zn(&(a * b));
+        assert_eq!(e1, e2);
+    }
+}
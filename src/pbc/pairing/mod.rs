@@ -0,0 +1,7 @@
+mod tate;
+mod gt;
+mod preprocessed;
+
+pub use tate::*;
+pub use gt::*;
+pub use preprocessed::*;
@@ -0,0 +1,99 @@
+use crate::pbc::elements::traits::*;
+use crate::pbc::curve::CurvePoint;
+use super::{MillerLines, Pairing, GT};
+use std::sync::Arc;
+
+/// pairing with a fixed first argument, like PBC's `pairing_pp_t`, which
+/// borrows the pairing.
+///
+/// The lines of the Miller loop only depend on the first argument, so their
+/// coefficients are calculated once and only evaluated in the second
+/// argument by `apply`, which needs no divisions.
+///
+/// ```
+/// use pbc4rust::prelude::*;
+///
+/// let options: CurveOptions = "type a
+/// q 27015232578650219
+/// h 12583020
+/// r 2146959361
+/// exp2 31
+/// exp1 19
+/// sign1 -1
+/// sign0 1".parse().unwrap();
+/// let pairing = Pairing::new(&options);
+/// let p = pairing.g1().random_element();
+/// let q = pairing.g1().random_element();
+///
+/// let pp = PairingPreprocessed::new(&pairing, &p);
+/// assert_eq!(pp.apply(&q), pairing.pair(&p, &q));
+/// ```
+#[derive(Debug, Clone)]
+pub struct PairingPreprocessed<'a> {
+    pairing: &'a Pairing,
+    p: CurvePoint,
+    lines: Option<MillerLines>,
+}
+
+impl<'a> PairingPreprocessed<'a> {
+    pub fn new(pairing: &'a Pairing, p: &CurvePoint) -> PairingPreprocessed<'a> {
+        assert_eq!(p.field(), pairing.g1());
+        let lines = if p.is_infinity() {
+            None
+        } else {
+            Some(pairing.lines(p))
+        };
+        PairingPreprocessed {
+            pairing,
+            p: p.clone(),
+            lines,
+        }
+    }
+
+    pub fn p(&self) -> &CurvePoint { &self.p }
+
+    /// calculates e(p, q)
+    pub fn apply(&self, q: &CurvePoint) -> GT {
+        assert_eq!(q.field(), self.pairing.g2());
+        match &self.lines {
+            Some(lines) if ! q.is_infinity() => {
+                self.pairing.gt().final_pow(&self.pairing.evaluate_lines(lines, q))
+            }
+            _ => Arc::clone(&self.pairing.gt()).one_element(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pbc::testlib::curve::type_a_options;
+
+    #[test]
+    fn test_apply() {
+        let pairing = Pairing::new(&type_a_options());
+        let p = pairing.g1().random_element();
+        let pp = PairingPreprocessed::new(&pairing, &p);
+        for _ in 0..5 {
+            let q = pairing.g1().random_element();
+            assert_eq!(pp.apply(&q), pairing.pair(&p, &q));
+        }
+
+        let a = pairing.zr().random_element();
+        let q = pairing.g1().random_element();
+        assert_eq!(pp.apply(&q.mul_zn(&a)), pairing.pair(&p, &q).pow_zn(&a));
+    }
+
+    #[test]
+    fn test_apply_to_infinity() {
+        let pairing = Pairing::new(&type_a_options());
+        let p = pairing.g1().random_element();
+        let infinity = pairing.g1().zero_element();
+
+        let pp = PairingPreprocessed::new(&pairing, &p);
+        assert!(pp.apply(&infinity).is_one());
+
+        let pp = PairingPreprocessed::new(&pairing, &infinity);
+        assert!(pp.apply(&p).is_one());
+    }
+}
@@ -0,0 +1,339 @@
+use crate::pbc::{Integer, PbcError};
+use crate::pbc::elements::traits::*;
+use crate::pbc::elements::{Quadratic, QuadraticField, Zr, ZrField};
+use super::{GT, GTField};
+use crate::pbc::curve::{CurveField, CurveOptions, CurvePoint, CurveType};
+use std::sync::Arc;
+
+/// reduced Tate pairing e: G1 × G1 → GT of a Type A curve.
+///
+/// The second argument is moved into the quadratic extension F_q[i] using
+/// the distortion map (x, y) ↦ (-x, iy), so that e(P, P) ≠ 1.
+#[derive(Debug, Clone)]
+pub struct Pairing {
+    options: CurveOptions,
+    g1: Arc<CurveField>,
+    gt: Arc<GTField>,
+    quadratic: Arc<QuadraticField<Zr, ZrField>>,
+    zr: Arc<ZrField>,
+}
+
+impl Pairing {
+    pub fn new(options: &CurveOptions) -> Pairing {
+        Self::try_new(options).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// fails for curves other than Type A
+    pub fn try_new(options: &CurveOptions) -> Result<Pairing, PbcError> {
+        match options.curve_type {
+            CurveType::A => (),
+            CurveType::A1 => return Err(PbcError::UnsupportedCurve(CurveType::A1)),
+        }
+
+        let g1 = Arc::new(CurveField::type_a(options));
+        let quadratic = Arc::new(QuadraticField::new(g1.target_field()));
+        let gt = Arc::new(GTField::new(Arc::clone(&quadratic), options.r.clone(), options.h.clone()));
+        let zr = Arc::new(ZrField::new(options.r.clone()));
+        Ok(Pairing {
+            options: options.clone(),
+            g1,
+            gt,
+            quadratic,
+            zr,
+        })
+    }
+
+    pub fn options(&self) -> &CurveOptions { &self.options }
+
+    /// the group of the first argument
+    pub fn g1(&self) -> Arc<CurveField> { Arc::clone(&self.g1) }
+
+    /// the group of the second argument, which is the same as G1 for
+    /// symmetric pairings
+    pub fn g2(&self) -> Arc<CurveField> { Arc::clone(&self.g1) }
+
+    pub fn gt(&self) -> Arc<GTField> { Arc::clone(&self.gt) }
+
+    /// the field of exponents, i.e. Z modulo the group order r
+    pub fn zr(&self) -> Arc<ZrField> { Arc::clone(&self.zr) }
+
+    pub fn pair(&self, p: &CurvePoint, q: &CurvePoint) -> GT {
+        self.try_pair(p, q).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// fails if the points do not belong to G1 and G2
+    pub fn try_pair(&self, p: &CurvePoint, q: &CurvePoint) -> Result<GT, PbcError> {
+        self.check_fields(p, q)?;
+        if p.is_infinity() || q.is_infinity() {
+            return Ok(Arc::clone(&self.gt).one_element());
+        }
+        Ok(self.gt().final_pow(&self.miller(p, q)))
+    }
+
+    /// calculates the product of e(p, q) over all pairs, like PBC's
+    /// `element_prod_pairing`.
+    ///
+    /// The Miller loops of all pairs share one accumulator, so that only
+    /// one final exponentiation is required.
+    pub fn multi_pair(&self, pairs: &[(CurvePoint, CurvePoint)]) -> GT {
+        self.try_multi_pair(pairs).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// fails if any of the points do not belong to G1 and G2
+    pub fn try_multi_pair(&self, pairs: &[(CurvePoint, CurvePoint)]) -> Result<GT, PbcError> {
+        let mut lines = Vec::with_capacity(pairs.len());
+        for (p, q) in pairs {
+            self.check_fields(p, q)?;
+            if ! (p.is_infinity() || q.is_infinity()) {
+                lines.push((self.lines(p), q));
+            }
+        }
+        if lines.is_empty() {
+            return Ok(Arc::clone(&self.gt).one_element());
+        }
+        let lines: Vec<_> = lines.iter().map(|(lines, q)| (lines, *q)).collect();
+        Ok(self.gt().final_pow(&self.evaluate_all_lines(&lines)))
+    }
+
+    fn check_fields(&self, p: &CurvePoint, q: &CurvePoint) -> Result<(), PbcError> {
+        if p.field() == self.g1 && q.field() == self.g2() {
+            Ok(())
+        } else {
+            Err(PbcError::FieldMismatch)
+        }
+    }
+
+    /// Miller loop for r = 2^exp2 + sign1·2^exp1 + sign0.
+    ///
+    /// All vertical lines evaluate to elements of F_q at the distorted
+    /// point, which are mapped to one by the final exponentiation, so they
+    /// are omitted. For the same reason, f⁻¹ can be replaced by the
+    /// conjugate of f.
+    fn miller(&self, p: &CurvePoint, q: &CurvePoint) -> Quadratic<Zr, ZrField> {
+        self.evaluate_lines(&self.lines(p), q)
+    }
+
+    /// calculates the lines of the Miller loop, which only depend on the
+    /// first argument
+    pub(super) fn lines(&self, p: &CurvePoint) -> MillerLines {
+        let mut tangents = Vec::with_capacity(self.options.exp2 as usize);
+        let mut v = p.clone();
+        let mut v1 = p.clone();
+        let three = Zr::new(Integer::from(3), p.x().field());
+
+        // one division per step, the doubled point is derived from the
+        // slope of the tangent
+        for i in 0..self.options.exp2 {
+            if i == self.options.exp1 {
+                v1 = if self.options.sign1 < 0 { -v.clone() } else { v.clone() };
+            }
+            let lambda = (v.x().square() * &three + self.g1.a()) / v.y().double();
+            let tangent = Line::new(lambda, &v);
+            v = tangent.double(&v);
+            tangents.push(tangent);
+        }
+
+        // the remaining ±1 only contributes a vertical line
+        let lambda = (v1.y().clone() - v.y()) / (v1.x().clone() - v.x());
+        let chord = Line::new(lambda, &v);
+        MillerLines { tangents, chord }
+    }
+
+    pub(super) fn evaluate_lines(&self, lines: &MillerLines, q: &CurvePoint) -> Quadratic<Zr, ZrField> {
+        self.evaluate_all_lines(&[(lines, q)])
+    }
+
+    /// runs the Miller loops of several pairings with a shared accumulator
+    fn evaluate_all_lines(&self, pairs: &[(&MillerLines, &CurvePoint)]) -> Quadratic<Zr, ZrField> {
+        let step = |f: Quadratic<Zr, ZrField>, i: usize| {
+            pairs.iter().fold(f, |f, (lines, q)| f * self.evaluate_line(&lines.tangents[i], q))
+        };
+
+        let mut f = Arc::clone(&self.quadratic).one_element();
+        for i in 0..self.options.exp1 as usize {
+            f = step(f.square(), i);
+        }
+
+        let f1 = if self.options.sign1 < 0 { f.conjugate() } else { f.clone() };
+        for i in self.options.exp1 as usize..self.options.exp2 as usize {
+            f = step(f.square(), i);
+        }
+        pairs.iter().fold(f * f1, |f, (lines, q)| f * self.evaluate_line(&lines.chord, q))
+    }
+
+    /// Y - y_v - λ(X - x_v) at (X, Y) = (-x_q, i·y_q)
+    fn evaluate_line(&self, line: &Line, q: &CurvePoint) -> Quadratic<Zr, ZrField> {
+        let x = &line.lambda * q.x() + &line.c;
+        Quadratic::new(x, q.y().clone(), Arc::clone(&self.quadratic))
+    }
+}
+
+/// line Y = λX + (y_v - λ·x_v) through some point v, stored as λ and
+/// c = λ·x_v - y_v
+#[derive(Debug, Clone)]
+pub(super) struct Line {
+    lambda: Zr,
+    c: Zr,
+}
+
+impl Line {
+    fn new(lambda: Zr, v: &CurvePoint) -> Line {
+        let c = &lambda * v.x() - v.y();
+        Line { lambda, c }
+    }
+
+    /// 2v, if this is the tangent at v: x = λ² - 2x_v and y = c - λx
+    fn double(&self, v: &CurvePoint) -> CurvePoint {
+        let x = self.lambda.square() - v.x().double();
+        let y = self.c.clone() - &self.lambda * &x;
+        CurvePoint::new(x, y, v.field())
+    }
+}
+
+/// the tangents of all doubling steps and the final chord of the Miller
+/// loop
+#[derive(Debug, Clone)]
+pub(super) struct MillerLines {
+    tangents: Vec<Line>,
+    chord: Line,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pbc::testlib::curve::type_a_options;
+
+    fn pairing() -> Pairing { Pairing::new(&type_a_options()) }
+
+    #[test]
+    fn test_non_degenerate() {
+        let pairing = pairing();
+        let p = pairing.g1().random_element();
+        let e = pairing.pair(&p, &p);
+        assert!(! e.is_one());
+    }
+
+    #[test]
+    fn test_linearity() {
+        let pairing = pairing();
+        let p = pairing.g1().random_element();
+        let q = pairing.g1().random_element();
+        let r = pairing.g1().random_element();
+
+        let e1 = pairing.pair(&(&p + &r), &q);
+        let e2 = pairing.pair(&p, &q) * pairing.pair(&r, &q);
+        assert_eq!(e1, e2);
+
+        let e1 = pairing.pair(&q, &(&p + &r));
+        let e2 = pairing.pair(&q, &p) * pairing.pair(&q, &r);
+        assert_eq!(e1, e2);
+    }
+
+    #[test]
+    fn test_bilinearity() {
+        let pairing = pairing();
+        let p = pairing.g1().random_element();
+        let q = pairing.g1().random_element();
+        let a = pairing.zr().random_element();
+
+        assert_eq!(pairing.pair(&p.mul_zn(&a), &q), pairing.pair(&p, &q.mul_zn(&a)));
+    }
+
+    #[test]
+    fn test_symmetry() {
+        let pairing = pairing();
+        let p = pairing.g1().random_element();
+        let q = pairing.g1().random_element();
+
+        assert_eq!(pairing.pair(&p, &q), pairing.pair(&q, &p));
+    }
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn test_send_sync() {
+        assert_send_sync::<Zr>();
+        assert_send_sync::<ZrField>();
+        assert_send_sync::<Quadratic<Zr, ZrField>>();
+        assert_send_sync::<CurvePoint>();
+        assert_send_sync::<GT>();
+        assert_send_sync::<Pairing>();
+        assert_send_sync::<crate::pbc::TypeACurveGenerator>();
+        assert_send_sync::<crate::pbc::TypeA1CurveGenerator>();
+    }
+
+    #[test]
+    fn test_pair_on_multiple_threads() {
+        static PAIRING: std::sync::OnceLock<Pairing> = std::sync::OnceLock::new();
+        let pairing = PAIRING.get_or_init(pairing);
+        let p = pairing.g1().random_element();
+        let q = pairing.g1().random_element();
+        let expected = pairing.pair(&p, &q);
+
+        let handles: Vec<_> = (0..4).map(|_| {
+            let (p, q) = (p.clone(), q.clone());
+            std::thread::spawn(move || PAIRING.get().unwrap().pair(&p, &q))
+        }).collect();
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_try_new() {
+        let options = crate::pbc::TypeA1CurveGenerator::new(2, 24).generate();
+        assert_eq!(Pairing::try_new(&options).unwrap_err(), PbcError::UnsupportedCurve(CurveType::A1));
+        let error = std::panic::catch_unwind(|| Pairing::new(&options)).unwrap_err();
+        assert_eq!(error.downcast_ref::<String>().unwrap(), &PbcError::UnsupportedCurve(CurveType::A1).to_string());
+        assert!(Pairing::try_new(&type_a_options()).is_ok());
+    }
+
+    #[test]
+    fn test_try_pair() {
+        let pairing = pairing();
+        let other = Pairing::new(&crate::pbc::TypeACurveGenerator::new(24, 64).generate());
+        let p = pairing.g1().random_element();
+        let q = other.g1().random_element();
+        assert_eq!(pairing.try_pair(&p, &p), Ok(pairing.pair(&p, &p)));
+        assert_eq!(pairing.try_pair(&p, &q), Err(PbcError::FieldMismatch));
+        assert_eq!(pairing.try_pair(&q, &p), Err(PbcError::FieldMismatch));
+        assert_eq!(pairing.try_multi_pair(&[(p.clone(), p.clone()), (p, q)]), Err(PbcError::FieldMismatch));
+    }
+
+    #[test]
+    fn test_lines() {
+        let pairing = pairing();
+        let p = pairing.g1().random_element();
+        let lines = pairing.lines(&p);
+        assert_eq!(lines.tangents.len(), pairing.options().exp2 as usize);
+
+        // the tangents run through p, 2p, 4p, ...
+        let mut v = p;
+        for tangent in lines.tangents.iter() {
+            assert_eq!(&tangent.lambda * v.x() - &tangent.c, v.y().clone());
+            assert_eq!(tangent.double(&v), v.double());
+            v = v.double();
+        }
+    }
+
+    #[test]
+    fn test_multi_pair() {
+        let pairing = pairing();
+        let pairs: Vec<_> = (0..4)
+            .map(|_| (pairing.g1().random_element(), pairing.g1().random_element()))
+            .collect();
+        let expected = pairs.iter()
+            .map(|(p, q)| pairing.pair(p, q))
+            .fold(pairing.gt().one_element(), |product, e| product * e);
+        assert_eq!(pairing.multi_pair(&pairs), expected);
+
+        // e(aP, Q) · e(-P, aQ) = 1
+        let (p, q) = pairs[0].clone();
+        let a = pairing.zr().random_element();
+        assert!(pairing.multi_pair(&[(p.mul_zn(&a), q.clone()), (-p.clone(), q.mul_zn(&a))]).is_one());
+
+        let infinity = pairing.g1().zero_element();
+        assert_eq!(pairing.multi_pair(&[(p.clone(), q.clone()), (infinity, q.clone())]), pairing.pair(&p, &q));
+        assert!(pairing.multi_pair(&[]).is_one());
+    }
+}
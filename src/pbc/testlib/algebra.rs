@@ -12,7 +12,7 @@ macro_rules! test_one {
             #[test]
             fn test_name() {
                 let field = $field;
-                let a = $field_type::random_element(Rc::clone(&field));
+                let a = $field_type::random_element(Arc::clone(&field));
                 let one:$elem_type $(< $($elem_param,)+ >)? = $field_type::one_element(field);
 
                 assert!(one.is_one());
@@ -33,7 +33,7 @@ macro_rules! test_zero {
             #[test]
             fn test_name() {
                 let field = $field;
-                let a = $field_type::random_element(Rc::clone(&field));
+                let a = $field_type::random_element(Arc::clone(&field));
                 let zero:$elem_type $(< $($elem_param,)+ >)? = $field_type::zero_element(field);
                 assert!(zero.is_zero());
                 assert_eq!(&a + &zero, a);
@@ -55,9 +55,9 @@ macro_rules! test_associativity {
             #[test]
             fn test_name() {
                 let field = $field;
-                let a = $field_type::random_element(Rc::clone(&field));
-                let b = $field_type::random_element(Rc::clone(&field));
-                let c = $field_type::random_element(Rc::clone(&field));
+                let a = $field_type::random_element(Arc::clone(&field));
+                let b = $field_type::random_element(Arc::clone(&field));
+                let c = $field_type::random_element(Arc::clone(&field));
                 let res1 = (&a).$op(&(&b).$op(&c));
                 let res2 = (&(&a).$op(&b)).$op(&c);
                 assert_eq!(res1, res2);
@@ -77,7 +77,7 @@ macro_rules! test_commutativity {
             #[test]
             fn test_name() {
                 let field = $field;
-                let a = $field_type::random_element(Rc::clone(&field));
+                let a = $field_type::random_element(Arc::clone(&field));
                 let b = $field_type::random_element(field);
                 let res1 = (&a).$op(&b);
                 let res2 = (&b).$op(&a);
@@ -121,8 +121,8 @@ macro_rules! test_distributivity {
             #[test]
             fn test_name() {
                 let field = $field;
-                let a = $field_type::random_element(Rc::clone(&field));
-                let b = $field_type::random_element(Rc::clone(&field));
+                let a = $field_type::random_element(Arc::clone(&field));
+                let b = $field_type::random_element(Arc::clone(&field));
                 let c = $field_type::random_element(field);
                 let res1 = (&a).$mul(&(&b).$add(&c));
                 let res2 = (&(&a).$mul(&b)).$add(&(&a).$mul(&c));
@@ -168,10 +168,170 @@ macro_rules! test_nqr {
             #[test]
             fn test_name() {
                 let field = $field;
-                let a = $field_type::nqr(Rc::clone(&field));
+                let a = $field_type::nqr(Arc::clone(&field));
                 let x = a.square();
                 assert_ne!(&a, &x);
             }
         });
     }
-}
\ No newline at end of file
+}
+#[macro_export]
+macro_rules! test_neg {
+    ($elem_type: ident $(< $( $elem_param: ty),+ >)?,
+     $field_type: ident $(< $( $field_param: ty),+ >)?,
+     $field:expr) => {
+        self::concat_idents!(test_name=test_neg_for_, $elem_type $(, $(_, $elem_param, )+ )? {
+            #[allow(non_snake_case)]
+            #[test]
+            fn test_name() {
+                let field = $field;
+                let a = $field_type::random_element(Arc::clone(&field));
+                let b = $field_type::random_element(Arc::clone(&field));
+                let zero:$elem_type $(< $($elem_param,)+ >)? = $field_type::zero_element(field);
+                assert_eq!(a.clone() + &a.clone().neg(), zero);
+                assert_eq!(a.clone() - &a, zero);
+                assert_eq!((a.clone() - &b) + &b, a);
+            }
+        });
+    }
+}
+
+#[macro_export]
+macro_rules! test_pow {
+    ($elem_type: ident $(< $( $elem_param: ty),+ >)?,
+     $field_type: ident $(< $( $field_param: ty),+ >)?,
+     $field:expr) => {
+        self::concat_idents!(test_name=test_pow_for_, $elem_type $(, $(_, $elem_param, )+ )? {
+            #[allow(non_snake_case)]
+            #[test]
+            fn test_name() {
+                let field = $field;
+                let a = $field_type::random_element(Arc::clone(&field));
+                let one:$elem_type $(< $($elem_param,)+ >)? = $field_type::one_element(Arc::clone(&field));
+                let zero:$elem_type $(< $($elem_param,)+ >)? = $field_type::zero_element(field);
+
+                let mut power = one;
+                let mut multiple = zero;
+                for i in 0..20 {
+                    assert_eq!(a.pow(&$crate::pbc::Integer::from(i)), power);
+                    power = power * &a;
+                    multiple = multiple + &a;
+                }
+
+                let exponents = std::sync::Arc::new($crate::pbc::ZrField::new($crate::pbc::Integer::from(ORDER)));
+                let n = $crate::pbc::Zr::new($crate::pbc::Integer::from(20), exponents);
+                assert_eq!(a.pow_zn(&n), power);
+                assert_eq!(a.mul_zn(&n), multiple);
+            }
+        });
+    }
+}
+
+#[macro_export]
+macro_rules! test_invert {
+    ($elem_type: ident $(< $( $elem_param: ty),+ >)?,
+     $field_type: ident $(< $( $field_param: ty),+ >)?,
+     $field:expr) => {
+        self::concat_idents!(test_name=test_invert_for_, $elem_type $(, $(_, $elem_param, )+ )? {
+            #[allow(non_snake_case)]
+            #[test]
+            fn test_name() {
+                let field = $field;
+                let a = $field_type::random_element(Arc::clone(&field));
+                let mut b = $field_type::random_element(Arc::clone(&field));
+                // zero has no inverse, unless it is the neutral element of a group
+                while b.is_zero() && ! b.is_one() {
+                    b = $field_type::random_element(Arc::clone(&field));
+                }
+                let one:$elem_type $(< $($elem_param,)+ >)? = $field_type::one_element(field);
+
+                let inverse = b.invert().unwrap();
+                assert_eq!(&b * &inverse, one);
+                assert_eq!((a.clone() / &b) * &b, a);
+                assert_eq!(b.pow(&$crate::pbc::Integer::from(-3)), inverse.square() * &inverse);
+            }
+        });
+    }
+}
+
+#[macro_export]
+macro_rules! test_bytes {
+    ($elem_type: ident $(< $( $elem_param: ty),+ >)?,
+     $field_type: ident $(< $( $field_param: ty),+ >)?,
+     $field:expr) => {
+        self::concat_idents!(test_name=test_bytes_for_, $elem_type $(, $(_, $elem_param, )+ )? {
+            #[allow(non_snake_case)]
+            #[test]
+            fn test_name() {
+                let field = $field;
+                let a = $field_type::random_element(Arc::clone(&field));
+                let bytes = a.to_bytes();
+                assert_eq!(bytes.len(), a.length_in_bytes());
+                assert_eq!(bytes.len(), field.length_in_bytes());
+
+                let b:$elem_type $(< $($elem_param,)+ >)? = FromBytes::from_bytes(Arc::clone(&field), &bytes).unwrap();
+                assert_eq!(a, b);
+
+                let result:Result<$elem_type $(< $($elem_param,)+ >)?, _> = FromBytes::from_bytes(field, &bytes[1..]);
+                assert_eq!(result, Err($crate::pbc::BytesError::InvalidLength { expected: bytes.len(), actual: bytes.len() - 1 }));
+            }
+        });
+    }
+}
+
+#[macro_export]
+macro_rules! test_pow_preprocessed {
+    ($elem_type: ident $(< $( $elem_param: ty),+ >)?,
+     $field_type: ident $(< $( $field_param: ty),+ >)?,
+     $field:expr) => {
+        self::concat_idents!(test_name=test_pow_preprocessed_for_, $elem_type $(, $(_, $elem_param, )+ )? {
+            #[allow(non_snake_case)]
+            #[test]
+            fn test_name() {
+                let field = $field;
+                let a = $field_type::random_element(Arc::clone(&field));
+                let pp = $crate::pbc::PowPreprocessed::new(&a, 32);
+
+                let mut exponents: Vec<$crate::pbc::Integer> = (0..40).map($crate::pbc::Integer::from).collect();
+                exponents.push($crate::pbc::Integer::from(u32::MAX));
+                exponents.push($crate::pbc::Integer::from(0x8000_0001_u32));
+                exponents.push($crate::pbc::Integer::from(u64::MAX));
+                exponents.push($crate::pbc::Integer::from(-12345));
+                for exp in exponents.iter() {
+                    assert_eq!(pp.pow(exp), a.pow(exp));
+                }
+
+                let exponents = std::sync::Arc::new($crate::pbc::ZrField::new($crate::pbc::Integer::from(ORDER)));
+                let n = $crate::pbc::ZrField::random_element(exponents);
+                assert_eq!(pp.pow_zn(&n), a.pow_zn(&n));
+            }
+        });
+    }
+}
+
+#[macro_export]
+macro_rules! test_multi_exp {
+    ($elem_type: ident $(< $( $elem_param: ty),+ >)?,
+     $field_type: ident $(< $( $field_param: ty),+ >)?,
+     $field:expr) => {
+        self::concat_idents!(test_name=test_multi_exp_for_, $elem_type $(, $(_, $elem_param, )+ )? {
+            #[allow(non_snake_case)]
+            #[test]
+            fn test_name() {
+                let field = $field;
+                let exponents = std::sync::Arc::new($crate::pbc::ZrField::new($crate::pbc::Integer::from(ORDER)));
+
+                // small inputs use Straus' algorithm, large ones Pippenger's
+                for n in [1, 2, 5, 40] {
+                    let bases: Vec<$elem_type $(< $($elem_param,)+ >)?> = (0..n).map(|_| $field_type::random_element(Arc::clone(&field))).collect();
+                    let mut scalars: Vec<_> = (0..n).map(|_| $crate::pbc::ZrField::random_element(Arc::clone(&exponents))).collect();
+                    scalars[0] = $crate::pbc::ZrField::zero_element(Arc::clone(&exponents));
+
+                    let expected = bases.iter().zip(scalars.iter())
+                        .fold($field_type::one_element(Arc::clone(&field)), |product, (base, scalar)| product * base.pow_zn(scalar));
+                    assert_eq!($crate::pbc::multi_exp(&bases, &scalars), expected);
+                }
+            }
+        });
+    }
+}
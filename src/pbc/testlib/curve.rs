@@ -0,0 +1,16 @@
+use crate::pbc::Integer;
+use crate::pbc::{CurveOptions, CurveType};
+
+/// a small Type A curve with r = 2^31 - 2^19 + 1
+pub fn type_a_options() -> CurveOptions {
+    CurveOptions {
+        curve_type: CurveType::A,
+        q: Integer::from(27015232578650219_u64),
+        h: Integer::from(12583020_u64),
+        r: Integer::from(2146959361_u64),
+        exp1: 19,
+        exp2: 31,
+        sign0: 1,
+        sign1: -1,
+    }
+}
@@ -1,4 +1,3 @@
 
 #[macro_use] pub mod algebra;
-
-pub use algebra::*;
\ No newline at end of file
+pub mod curve;